@@ -0,0 +1,71 @@
+//! The interface between the CPU and everything it is wired to
+
+/// A 16-bit address bus. Reads may have side effects on devices, such as
+/// clearing a status register, so anything that only inspects the bus, like
+/// a debugger or a tracer, should go through `peek` instead.
+pub trait Bus {
+    fn read(&mut self, address: u16) -> u8;
+
+    fn write(&mut self, address: u16, value: u8);
+
+    /// Reads without any side effects.
+    fn peek(&self, address: u16) -> u8;
+
+    /// Writes without any side effects, into ROM as well, for loaders and
+    /// debuggers. Writes to devices are dropped.
+    fn poke(&mut self, address: u16, value: u8) {
+        self.write(address, value);
+    }
+
+    /// Whether `poke` stores anything at `address`, which it does not for
+    /// devices or addresses nothing is mapped to.
+    fn can_poke(&self, address: u16) -> bool {
+        true
+    }
+
+    /// Lets the devices on the bus catch up with the CPU, which calls this
+    /// after every step with its total cycle count.
+    fn sync(&mut self, cycles: u64) {}
+
+    /// Whether a device on the bus pulls the IRQ line low.
+    fn irq(&self) -> bool {
+        false
+    }
+
+    /// Whether a device on the bus triggered an NMI since the last call.
+    fn take_nmi(&mut self) -> bool {
+        false
+    }
+
+    /// Address of an access the bus refused since the last call, if any.
+    fn take_fault(&mut self) -> Option<u16> {
+        None
+    }
+
+    /// Exit status a device asked the emulator to stop with, if any.
+    fn exit_code(&self) -> Option<u8> {
+        None
+    }
+
+    /// Bank currently paged in at `address`, if it lies in a banked region.
+    fn bank(&self, address: u16) -> Option<usize> {
+        None
+    }
+
+    fn read_word(&mut self, addr: u16) -> u16 {
+        let lower = self.read(addr) as u16;
+        let upper = self.read(addr.wrapping_add(1)) as u16;
+        upper << 8 | lower
+    }
+
+    fn write_word(&mut self, addr: u16, val: u16) {
+        self.write(addr, val as u8);
+        self.write(addr.wrapping_add(1), (val >> 8) as u8);
+    }
+
+    fn peek_word(&self, addr: u16) -> u16 {
+        let lower = self.peek(addr) as u16;
+        let upper = self.peek(addr.wrapping_add(1)) as u16;
+        upper << 8 | lower
+    }
+}
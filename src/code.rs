@@ -1,25 +1,360 @@
-//! Helpers for reading and uploading executables into the ROM
+//! Helpers for reading executables and loading them into memory, as flat
+//! binaries, Intel HEX or Motorola S-records
 
+use crate::bus::Bus;
 use crate::cpu::Cpu;
+use crate::error::{Result, VolveError};
+use crate::memory::{RESET, ROM_HIGH_ADDRESS, ROM_LOW_ADDRESS};
 use std::convert::TryInto;
-use std::fs::File;
+use std::fmt::Write;
+use std::fs::{self, File};
 use std::io::Read;
+use std::ops::RangeInclusive;
 use std::path::Path;
 
-pub fn read_file<P: AsRef<Path>>(path: P) -> Vec<u8> {
-    let mut file = File::open(path).expect("Failed to open the binary file");
+pub fn read_file<P: AsRef<Path>>(path: P) -> Result<Vec<u8>> {
+    let mut file = File::open(path)?;
     let mut contents = Vec::new();
 
-    file.read_to_end(&mut contents).unwrap();
+    file.read_to_end(&mut contents)?;
 
-    contents
+    Ok(contents)
 }
 
-pub fn upload_to_rom(cpu: &mut Cpu, content: Vec<u8>) {
-    let mut address = 0x7FFF;
+/// Loads `image` at `address`. Fails if the image runs past the end of
+/// the address space, in which case nothing is written.
+pub fn load<B: Bus>(bus: &mut B, address: u16, image: &[u8]) -> Result<()> {
+    load_region(bus, address..=0xFFFF, image)
+}
+
+/// Loads `image` at the start of `region`, which it has to fit into. Goes
+/// through `Bus::poke`, so images can be loaded into ROM as well, and
+/// several images can be loaded into one machine, like a RAM preload next
+/// to the ROM. Fails without writing anything if the image covers a device
+/// or an unmapped address, which `poke` would skip.
+pub fn load_region<B: Bus>(bus: &mut B, region: RangeInclusive<u16>, image: &[u8]) -> Result<()> {
+    check_fits(image, &region)?;
+    let mut covered = region.clone().take(image.len());
+    if let Some(address) = covered.find(|&address| !bus.can_poke(address)) {
+        return Err(VolveError::NotLoadable { address });
+    }
+
+    for (address, &byte) in region.zip(image) {
+        bus.poke(address, byte);
+    }
+    Ok(())
+}
+
+/// Loads `content` into the ROM, from $8000 up.
+pub fn upload_to_rom<B: Bus>(cpu: &mut Cpu<B>, content: Vec<u8>) -> Result<()> {
+    load_region(&mut cpu.bus, ROM_LOW_ADDRESS..=ROM_HIGH_ADDRESS, &content)
+}
+
+pub(crate) fn check_fits(image: &[u8], region: &RangeInclusive<u16>) -> Result<()> {
+    let capacity = (*region.end() as usize + 1).saturating_sub(*region.start() as usize);
+    if image.len() > capacity {
+        return Err(VolveError::ImageTooLarge {
+            size: image.len(),
+            capacity,
+        });
+    }
+    Ok(())
+}
+
+/// Data bytes per record when exporting.
+const RECORD_SIZE: usize = 16;
+
+const OUTSIDE: &str = "address outside of the 64K address space";
+
+/// Text formats for program images, as produced by assemblers and EEPROM
+/// programmers.
+#[derive(Copy, Debug, Clone, PartialEq, Eq)]
+pub enum Format {
+    IntelHex,
+    /// Motorola S-records, S19, S28 or S37.
+    SRecord,
+}
+
+impl Format {
+    /// Guesses the format from the file extension, `None` for anything that
+    /// is not a known text format and so probably a flat binary.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Option<Format> {
+        let extension = path.as_ref().extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "hex" | "ihx" | "ihex" => Some(Format::IntelHex),
+            "s19" | "s28" | "s37" | "srec" | "mot" => Some(Format::SRecord),
+            _ => None,
+        }
+    }
+}
+
+/// Consecutive bytes of an image.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Segment {
+    pub address: u16,
+    pub data: Vec<u8>,
+}
+
+/// A program image read from an Intel HEX or S-record file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Image {
+    pub segments: Vec<Segment>,
+    /// Entry point given by a start address record. S-records cannot tell
+    /// an entry point at $0000 from none at all, so they never give that.
+    pub start: Option<u16>,
+}
+
+impl Image {
+    pub fn read<P: AsRef<Path>>(path: P, format: Format) -> Result<Image> {
+        Image::parse(&fs::read_to_string(path)?, format)
+    }
+
+    /// Parses the records of `text`, verifying their checksums.
+    pub fn parse(text: &str, format: Format) -> Result<Image> {
+        match format {
+            Format::IntelHex => parse_intel_hex(text),
+            Format::SRecord => parse_srecord(text),
+        }
+    }
+
+    /// Loads the segments into `bus`. When the image has a start address
+    /// and the reset vector is still empty, $0000 or $FFFF like erased
+    /// memory, the start address is written to the vector, so that the CPU
+    /// comes out of reset there. A vector set by the image or by a ROM
+    /// loaded before it is left alone.
+    pub fn load<B: Bus>(&self, bus: &mut B) -> Result<()> {
+        for segment in &self.segments {
+            load(bus, segment.address, &segment.data)?;
+        }
+
+        if let Some(start) = self.start {
+            let covered = self.covers(RESET) || self.covers(RESET + 1);
+            if !covered && matches!(bus.peek_word(RESET), 0x0000 | 0xFFFF) {
+                load(bus, RESET, &start.to_le_bytes())?;
+            }
+        }
+        Ok(())
+    }
+
+    fn covers(&self, address: u16) -> bool {
+        self.segments.iter().any(|segment| {
+            address >= segment.address
+                && ((address - segment.address) as usize) < segment.data.len()
+        })
+    }
+
+    /// Appends `data` at `address`, extending the last segment if it ends
+    /// right there. Fails if the data does not fit into 64K.
+    fn add(&mut self, address: u32, data: &[u8]) -> std::result::Result<(), &'static str> {
+        if address as usize + data.len() > 0x10000 {
+            return Err(OUTSIDE);
+        }
+        let address = address as u16;
 
-    for byte in content {
-        address += 1;
-        cpu.memory.write_byte(address, byte);
+        match self.segments.last_mut() {
+            Some(last) if last.address as usize + last.data.len() == address as usize => {
+                last.data.extend_from_slice(data)
+            }
+            _ => self.segments.push(Segment {
+                address,
+                data: data.to_vec(),
+            }),
+        }
+        Ok(())
     }
 }
+
+/// Writes the contents of `range` as seen through `bus.peek`, with `start`
+/// as the entry point if given.
+pub fn export<B: Bus>(
+    bus: &B,
+    range: RangeInclusive<u16>,
+    start: Option<u16>,
+    format: Format,
+) -> String {
+    let bytes: Vec<u8> = range.clone().map(|address| bus.peek(address)).collect();
+    let records = bytes
+        .chunks(RECORD_SIZE)
+        .enumerate()
+        .map(|(index, chunk)| (range.start() + (index * RECORD_SIZE) as u16, chunk));
+
+    match format {
+        Format::IntelHex => export_intel_hex(records, start),
+        Format::SRecord => export_srecord(records, start),
+    }
+}
+
+fn parse_intel_hex(text: &str) -> Result<Image> {
+    let mut image = Image::default();
+    // upper address bits from extended segment and linear address records
+    let mut base = 0u32;
+
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let bad = |reason| VolveError::BadRecord {
+            line: index + 1,
+            reason,
+        };
+
+        let hex = line.strip_prefix(':').ok_or_else(|| bad("missing ':'"))?;
+        let bytes = decode_hex(hex).ok_or_else(|| bad("invalid hex digits"))?;
+        if bytes.len() < 5 || bytes.len() != bytes[0] as usize + 5 {
+            return Err(bad("wrong record length"));
+        }
+        if checksum(&bytes) != 0 {
+            return Err(bad("checksum mismatch"));
+        }
+
+        let offset = u16::from_be_bytes([bytes[1], bytes[2]]) as u32;
+        let data = &bytes[4..bytes.len() - 1];
+        let value = data
+            .iter()
+            .fold(0u32, |value, &byte| value << 8 | byte as u32);
+        match (bytes[3], data.len()) {
+            (0x00, _) => image.add(base + offset, data).map_err(bad)?,
+            (0x01, _) => break,
+            (0x02, 2) => base = value << 4,
+            (0x04, 2) => base = value << 16,
+            // CS:IP and EIP, only meaningful as a 16-bit address here
+            (0x03, 4) | (0x05, 4) => {
+                let start = match bytes[3] {
+                    0x03 => (value >> 16 << 4) + (value & 0xFFFF),
+                    _ => value,
+                };
+                image.start = Some(start.try_into().map_err(|_| bad(OUTSIDE))?);
+            }
+            (0x02..=0x05, _) => return Err(bad("wrong record length")),
+            _ => return Err(bad("unknown record type")),
+        }
+    }
+    Ok(image)
+}
+
+fn parse_srecord(text: &str) -> Result<Image> {
+    let mut image = Image::default();
+
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let bad = |reason| VolveError::BadRecord {
+            line: index + 1,
+            reason,
+        };
+
+        let record = line.strip_prefix('S').ok_or_else(|| bad("missing 'S'"))?;
+        let kind = record
+            .chars()
+            .next()
+            .ok_or_else(|| bad("missing record type"))?;
+        let width = match kind {
+            '0' | '1' | '5' | '9' => 2,
+            '2' | '6' | '8' => 3,
+            '3' | '7' => 4,
+            _ => return Err(bad("unknown record type")),
+        };
+        let bytes = decode_hex(&record[1..]).ok_or_else(|| bad("invalid hex digits"))?;
+        if bytes.len() < width + 2 || bytes.len() != bytes[0] as usize + 1 {
+            return Err(bad("wrong record length"));
+        }
+        if checksum(&bytes) != 0xFF {
+            return Err(bad("checksum mismatch"));
+        }
+
+        let address = bytes[1..=width]
+            .iter()
+            .fold(0u32, |address, &byte| address << 8 | byte as u32);
+        let data = &bytes[width + 1..bytes.len() - 1];
+        match kind {
+            '1' | '2' | '3' => image.add(address, data).map_err(bad)?,
+            // the termination record is mandatory, tools fill in zero when
+            // there is no entry point
+            '7' | '8' | '9' => {
+                if address != 0 {
+                    image.start = Some(address.try_into().map_err(|_| bad(OUTSIDE))?);
+                }
+                break;
+            }
+            // header and record counts
+            _ => {}
+        }
+    }
+    Ok(image)
+}
+
+fn export_intel_hex<'a, I>(records: I, start: Option<u16>) -> String
+where
+    I: Iterator<Item = (u16, &'a [u8])>,
+{
+    let mut text = String::new();
+    let mut line = |kind: u8, address: u16, data: &[u8]| {
+        let mut bytes = vec![data.len() as u8];
+        bytes.extend_from_slice(&address.to_be_bytes());
+        bytes.push(kind);
+        bytes.extend_from_slice(data);
+        bytes.push(0u8.wrapping_sub(checksum(&bytes)));
+        writeln!(text, ":{}", encode_hex(&bytes)).unwrap();
+    };
+
+    for (address, data) in records {
+        line(0x00, address, data);
+    }
+    if let Some(start) = start {
+        line(0x05, 0, &(start as u32).to_be_bytes());
+    }
+    line(0x01, 0, &[]);
+    text
+}
+
+fn export_srecord<'a, I>(records: I, start: Option<u16>) -> String
+where
+    I: Iterator<Item = (u16, &'a [u8])>,
+{
+    let mut text = String::new();
+    let mut line = |kind: char, address: u16, data: &[u8]| {
+        let mut bytes = vec![data.len() as u8 + 3];
+        bytes.extend_from_slice(&address.to_be_bytes());
+        bytes.extend_from_slice(data);
+        bytes.push(!checksum(&bytes));
+        writeln!(text, "S{}{}", kind, encode_hex(&bytes)).unwrap();
+    };
+
+    line('0', 0, &[]);
+    let mut count = 0;
+    for (address, data) in records {
+        line('1', address, data);
+        count += 1;
+    }
+    line('5', count, &[]);
+    // S9 is required to end the file, $0000 when there is no entry point,
+    // which reads back as none
+    line('9', start.unwrap_or(0), &[]);
+    text
+}
+
+fn checksum(bytes: &[u8]) -> u8 {
+    bytes.iter().fold(0u8, |sum, &byte| sum.wrapping_add(byte))
+}
+
+/// Decodes pairs of hex digits, failing on anything else, including a
+/// dangling digit at the end.
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    hex.as_bytes()
+        .chunks(2)
+        .map(|pair| match *pair {
+            [high, low] => {
+                let digit = |byte: u8| (byte as char).to_digit(16);
+                Some((digit(high)? << 4 | digit(low)?) as u8)
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02X}", byte)).collect()
+}
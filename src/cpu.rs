@@ -1,56 +1,341 @@
-use crate::instruction::{AddressingMode, Instruction, OpCode, OP_CODES};
-use crate::memory::Memory;
+use crate::bus::Bus;
+use crate::error::{Result, VolveError};
+use crate::instruction::{AddressingMode, Instruction, OpCode};
+use crate::memory::{Memory, IRQ, NMI, RESET, STACK_LOW_ADDRESS};
 use crate::registers::{Registers, StatusFlag};
-use std::ops::Not;
+use crate::variant::CpuVariant;
+
+/// Operand of an instruction, as resolved from its addressing mode.
+#[derive(Copy, Debug, Clone)]
+pub struct Operand {
+    /// Effective address, `None` for accumulator and immediate operands.
+    pub address: Option<u16>,
+    pub value: u8,
+    /// Whether indexing carried the address into another page.
+    pub page_crossed: bool,
+}
+
+/// Execution state, changed by the WAI and STP instructions.
+#[derive(Copy, Debug, Clone, PartialEq, Eq)]
+pub enum State {
+    Running,
+    /// Halted by WAI until an interrupt line becomes active.
+    Waiting,
+    /// Halted by STP, or a JAM on NMOS parts, until the next reset.
+    Stopped,
+}
 
-pub struct Cpu {
+pub struct Cpu<B: Bus = Memory> {
     pub registers: Registers,
-    pub memory: Memory,
+    pub bus: B,
+    variant: CpuVariant,
+    cycles: u64,
+    // cycles the current instruction spends on top of its base count
+    penalty_cycles: u8,
+    irq: bool,
+    nmi_pending: bool,
+    state: State,
+    magic: u8,
+}
+
+impl Default for Cpu {
+    fn default() -> Cpu {
+        Cpu::new()
+    }
 }
 
 impl Cpu {
     pub fn new() -> Cpu {
+        Cpu::with_variant(CpuVariant::default())
+    }
+
+    pub fn with_variant(variant: CpuVariant) -> Cpu {
+        Cpu::with_bus(Memory::new(), variant)
+    }
+}
+
+impl<B: Bus> Cpu<B> {
+    /// Creates a CPU wired to `bus` instead of a plain 64K memory.
+    pub fn with_bus(bus: B, variant: CpuVariant) -> Cpu<B> {
         Cpu {
             registers: Registers::new(),
-            memory: Memory::new(),
+            bus,
+            variant,
+            cycles: 0,
+            penalty_cycles: 0,
+            irq: false,
+            nmi_pending: false,
+            state: State::Running,
+            magic: 0xEE,
         }
     }
 
-    pub fn run(&mut self) {
-        self.registers.pc = self.memory.read_word(0xFFFC);
+    /// Resets the CPU and runs it until a device asks to exit. Ends with an
+    /// error when the CPU is stopped or a step fails.
+    pub fn run(&mut self) -> Result<()> {
+        self.reset();
 
-        while self.is_finished().not() {
-            self.step()
+        while self.bus.exit_code().is_none() {
+            if self.state == State::Stopped {
+                return Err(VolveError::Halted);
+            }
+            self.step()?;
         }
+        Ok(())
     }
 
-    fn is_finished(&mut self) -> bool {
-        // cpu shutdowns when invalid opcode is hit
-        if (OP_CODES[self.fetch_insn() as usize].is_none()) {
-            true
-        } else {
-            false
+    pub fn variant(&self) -> CpuVariant {
+        self.variant
+    }
+
+    /// Sets the "magic constant" of the unstable NMOS opcodes ANE and LXA.
+    /// The bits it contributes depend on the individual chip and even its
+    /// temperature, common values are $00, $EE and $FF.
+    pub fn set_magic(&mut self, magic: u8) {
+        self.magic = magic;
+    }
+
+    pub fn state(&self) -> State {
+        self.state
+    }
+
+    /// Number of cycles the CPU has run for since it was created.
+    pub fn cycles(&self) -> u64 {
+        self.cycles
+    }
+
+    /// Single line dump of the registers and the next opcode for trace
+    /// output, along with the bank paged in at pc if it lies in a banked
+    /// region. Goes through `peek`, so tracing never disturbs devices.
+    pub fn trace(&self) -> String {
+        let pc = self.registers.pc;
+        let bank = match self.bus.bank(pc) {
+            Some(bank) => format!(" [bank {}]", bank),
+            None => String::new(),
+        };
+
+        format!(
+            "{:04X}{} {:02X}  A:{:02X} X:{:02X} Y:{:02X} P:{:02X} SP:{:02X} CYC:{}",
+            pc,
+            bank,
+            self.bus.peek(pc),
+            self.registers.a,
+            self.registers.x,
+            self.registers.y,
+            self.registers.p,
+            self.registers.sp,
+            self.cycles
+        )
+    }
+
+    /// Runs the reset sequence: three suppressed stack pushes, interrupts
+    /// masked and pc loaded from the RESET vector. The CMOS parts also clear
+    /// decimal mode, which is left undefined on the NMOS 6502.
+    pub fn reset(&mut self) {
+        self.registers.sp = self.registers.sp.wrapping_sub(3);
+        self.registers.set_flag(StatusFlag::NoInterrupts, true);
+        if self.variant.is_cmos() {
+            self.registers.set_flag(StatusFlag::Decimal, false);
+        }
+        self.registers.pc = self.bus.read_word(RESET);
+        self.nmi_pending = false;
+        self.state = State::Running;
+        self.cycles += 7;
+    }
+
+    /// Pulls the IRQ line low. The request stays active until it is released
+    /// and is only serviced while the I flag is clear.
+    pub fn assert_irq(&mut self) {
+        self.irq = true;
+    }
+
+    pub fn release_irq(&mut self) {
+        self.irq = false;
+    }
+
+    /// Signals a falling edge on the NMI line. The interrupt is serviced once
+    /// before the next instruction, regardless of the I flag.
+    pub fn trigger_nmi(&mut self) {
+        self.nmi_pending = true;
+    }
+
+    /// Pushes the return address and P, masks interrupts and continues at the
+    /// address stored in `vector`. `brk` tells BRK apart from IRQ and NMI in
+    /// the pushed copy of P.
+    fn interrupt(&mut self, return_addr: u16, vector: u16, brk: bool) {
+        self.push_word(return_addr);
+        self.push_status(brk);
+        self.registers.set_flag(StatusFlag::NoInterrupts, true);
+        if self.variant.is_cmos() {
+            self.registers.set_flag(StatusFlag::Decimal, false);
         }
+        self.registers.pc = self.bus.read_word(vector);
     }
 
-    fn step(&mut self) {
+    fn service_interrupt(&mut self, vector: u16) -> u8 {
+        self.interrupt(self.registers.pc, vector, false);
+        self.cycles += 7;
+        7
+    }
+
+    /// Services a pending interrupt or executes a single instruction and
+    /// returns the number of cycles it took. While the CPU is halted, a step
+    /// idles for a single cycle instead.
+    ///
+    /// Afterwards the bus gets to catch up, and any NMI raised by one of its
+    /// devices is latched for the next step.
+    pub fn step(&mut self) -> Result<u8> {
+        let cycles = self.execute_step()?;
+
+        self.bus.sync(self.cycles);
+        if self.bus.take_nmi() {
+            self.nmi_pending = true;
+        }
+        match self.bus.take_fault() {
+            Some(address) => Err(VolveError::BusFault { address }),
+            None => Ok(cycles),
+        }
+    }
+
+    /// Whether the IRQ line is low, either through `assert_irq` or because a
+    /// device on the bus pulls it.
+    fn irq_line(&self) -> bool {
+        self.irq || self.bus.irq()
+    }
+
+    fn execute_step(&mut self) -> Result<u8> {
+        match self.state {
+            State::Stopped => return Ok(self.idle()),
+            // an interrupt ends WAI even when I is set, execution then simply
+            // continues after the WAI instead of jumping to the handler
+            State::Waiting if self.nmi_pending || self.irq_line() => self.state = State::Running,
+            State::Waiting => return Ok(self.idle()),
+            State::Running => {}
+        }
+
+        if self.nmi_pending {
+            self.nmi_pending = false;
+            return Ok(self.service_interrupt(NMI));
+        }
+        if self.irq_line() && !self.registers.get_flag(StatusFlag::NoInterrupts) {
+            return Ok(self.service_interrupt(IRQ));
+        }
+
         let bytecode = self.fetch_insn();
-        let insn = self.decode_bytecode(bytecode);
+        let insn = self.decode_bytecode(bytecode)?;
+        self.penalty_cycles = 0;
         self.execute_insn(insn);
+
+        // jumps, branches and returns have already loaded the new pc
+        if !insn.opcode.transfers_control() {
+            self.registers.pc = self.registers.pc.wrapping_add(insn.length as u16);
+        }
+
+        let cycles = insn.cycles + self.penalty_cycles;
+        self.cycles += cycles as u64;
+        Ok(cycles)
+    }
+
+    fn idle(&mut self) -> u8 {
+        self.cycles += 1;
+        1
     }
 
     fn fetch_insn(&mut self) -> u8 {
-        self.memory.read_byte(self.registers.pc)
+        self.bus.read(self.registers.pc)
     }
 
-    fn decode_bytecode(&self, bytecode: u8) -> Instruction {
-        OP_CODES[bytecode as usize].expect("Invalid instruction hit!")
+    fn decode_bytecode(&self, bytecode: u8) -> Result<Instruction> {
+        self.variant
+            .decode(bytecode)
+            .ok_or(VolveError::InvalidOpcode {
+                opcode: bytecode,
+                address: self.registers.pc as u32,
+            })
+    }
+
+    /// Pushes a byte onto the stack. The stack pointer wraps around within
+    /// page $01, so the stack never leaves it.
+    pub fn push_byte(&mut self, value: u8) {
+        self.bus
+            .write(STACK_LOW_ADDRESS + self.registers.sp as u16, value);
+        self.registers.sp = self.registers.sp.wrapping_sub(1);
+    }
+
+    pub fn pull_byte(&mut self) -> u8 {
+        self.registers.sp = self.registers.sp.wrapping_add(1);
+        self.bus.read(STACK_LOW_ADDRESS + self.registers.sp as u16)
+    }
+
+    /// Pushes a word high byte first, so it ends up little endian in memory.
+    pub fn push_word(&mut self, value: u16) {
+        self.push_byte((value >> 8) as u8);
+        self.push_byte(value as u8);
+    }
+
+    pub fn pull_word(&mut self) -> u16 {
+        let lower = self.pull_byte() as u16;
+        let upper = self.pull_byte() as u16;
+        upper << 8 | lower
+    }
+
+    /// Pushes P with the unused bit set. B only exists in the pushed copy and
+    /// is set for BRK and PHP, but clear for IRQ and NMI.
+    fn push_status(&mut self, brk: bool) {
+        let mut p = self.registers.p | StatusFlag::Unused as u8;
+
+        if brk {
+            p |= StatusFlag::Break as u8;
+        } else {
+            p &= !(StatusFlag::Break as u8);
+        }
+        self.push_byte(p);
+    }
+
+    /// Pulls P for PLP and RTI. Bits 4 and 5 do not exist in the register and
+    /// are left untouched.
+    fn pull_status(&mut self) {
+        let ignored = StatusFlag::Break as u8 | StatusFlag::Unused as u8;
+        let value = self.pull_byte();
+        self.registers.p = (value & !ignored) | (self.registers.p & ignored);
+    }
+
+    /// Takes the branch when `condition` holds and falls through to the next
+    /// instruction otherwise. The displacement is always the last operand byte
+    /// and counts from the address of the next instruction.
+    ///
+    /// A taken branch costs one extra cycle, and one more if the target lies
+    /// in a different page than the next instruction.
+    fn branch(&mut self, mode: AddressingMode, condition: bool) {
+        let length = match mode {
+            AddressingMode::Relative => 2,
+            AddressingMode::ZeroPageRelative => 3,
+            _ => unreachable!("Invalid addressing mode {:?} for a branch", mode),
+        };
+        let next = self.registers.pc.wrapping_add(length);
+
+        if condition {
+            let displacement = self.bus.read(next.wrapping_sub(1)) as i8;
+            let target = next.wrapping_add(displacement as u16);
+
+            self.penalty_cycles += 1;
+            if (target & 0xFF00) != (next & 0xFF00) {
+                self.penalty_cycles += 1;
+            }
+            self.registers.pc = target;
+        } else {
+            self.registers.pc = next;
+        }
     }
 
     pub fn execute_insn(&mut self, insn: Instruction) {
         match insn.opcode {
             OpCode::ADC => self.adc(insn.mode),
+            OpCode::ALR => self.alr(insn.mode),
+            OpCode::ANC => self.anc(insn.mode),
             OpCode::AND => self.and(insn.mode),
+            OpCode::ANE => self.ane(insn.mode),
+            OpCode::ARR => self.arr(insn.mode),
             OpCode::ASL => self.asl(insn.mode),
             OpCode::BBR0 => self.bbr(insn.mode, 0),
             OpCode::BBR1 => self.bbr(insn.mode, 1),
@@ -75,6 +360,7 @@ impl Cpu {
             OpCode::BMI => self.bmi(insn.mode),
             OpCode::BNE => self.bne(insn.mode),
             OpCode::BPL => self.bpl(insn.mode),
+            OpCode::BRA => self.bra(insn.mode),
             OpCode::BRK => self.brk(insn.mode),
             OpCode::BVC => self.bvc(insn.mode),
             OpCode::BVS => self.bvs(insn.mode),
@@ -94,28 +380,35 @@ impl Cpu {
             OpCode::INX => self.inx(insn.mode),
             OpCode::INY => self.iny(insn.mode),
             OpCode::ISB => self.isb(insn.mode),
+            OpCode::JAM => self.jam(insn.mode),
             OpCode::JMP => self.jmp(insn.mode),
             OpCode::JSR => self.jsr(insn.mode),
+            OpCode::LAS => self.las(insn.mode),
             OpCode::LAX => self.lax(insn.mode),
             OpCode::LDA => self.lda(insn.mode),
             OpCode::LDX => self.ldx(insn.mode),
             OpCode::LDY => self.ldy(insn.mode),
             OpCode::LSR => self.lsr(insn.mode),
+            OpCode::LXA => self.lxa(insn.mode),
             OpCode::NOP => self.nop(insn.mode),
             OpCode::ORA => self.ora(insn.mode),
             OpCode::PHA => self.pha(insn.mode),
             OpCode::PHP => self.php(insn.mode),
+            OpCode::PHX => self.phx(insn.mode),
+            OpCode::PHY => self.phy(insn.mode),
             OpCode::PLA => self.pla(insn.mode),
             OpCode::PLP => self.plp(insn.mode),
+            OpCode::PLX => self.plx(insn.mode),
+            OpCode::PLY => self.ply(insn.mode),
             OpCode::RLA => self.rla(insn.mode),
-            OpCode::RMB0 => self.rmb0(insn.mode),
-            OpCode::RMB1 => self.rmb1(insn.mode),
-            OpCode::RMB2 => self.rmb2(insn.mode),
-            OpCode::RMB3 => self.rmb3(insn.mode),
-            OpCode::RMB4 => self.rmb4(insn.mode),
-            OpCode::RMB5 => self.rmb5(insn.mode),
-            OpCode::RMB6 => self.rmb6(insn.mode),
-            OpCode::RMB7 => self.rmb7(insn.mode),
+            OpCode::RMB0 => self.rmb(insn.mode, 0),
+            OpCode::RMB1 => self.rmb(insn.mode, 1),
+            OpCode::RMB2 => self.rmb(insn.mode, 2),
+            OpCode::RMB3 => self.rmb(insn.mode, 3),
+            OpCode::RMB4 => self.rmb(insn.mode, 4),
+            OpCode::RMB5 => self.rmb(insn.mode, 5),
+            OpCode::RMB6 => self.rmb(insn.mode, 6),
+            OpCode::RMB7 => self.rmb(insn.mode, 7),
             OpCode::ROL => self.rol(insn.mode),
             OpCode::ROR => self.ror(insn.mode),
             OpCode::RRA => self.rra(insn.mode),
@@ -123,14 +416,29 @@ impl Cpu {
             OpCode::RTS => self.rts(insn.mode),
             OpCode::SAX => self.sax(insn.mode),
             OpCode::SBC => self.sbc(insn.mode),
+            OpCode::SBX => self.sbx(insn.mode),
             OpCode::SEC => self.sec(insn.mode),
             OpCode::SED => self.sed(insn.mode),
             OpCode::SEI => self.sei(insn.mode),
+            OpCode::SHA => self.sha(insn.mode),
+            OpCode::SHX => self.shx(insn.mode),
+            OpCode::SHY => self.shy(insn.mode),
             OpCode::SLO => self.slo(insn.mode),
+            OpCode::SMB0 => self.smb(insn.mode, 0),
+            OpCode::SMB1 => self.smb(insn.mode, 1),
+            OpCode::SMB2 => self.smb(insn.mode, 2),
+            OpCode::SMB3 => self.smb(insn.mode, 3),
+            OpCode::SMB4 => self.smb(insn.mode, 4),
+            OpCode::SMB5 => self.smb(insn.mode, 5),
+            OpCode::SMB6 => self.smb(insn.mode, 6),
+            OpCode::SMB7 => self.smb(insn.mode, 7),
             OpCode::SRE => self.sre(insn.mode),
             OpCode::STA => self.sta(insn.mode),
+            OpCode::STP => self.stp(insn.mode),
             OpCode::STX => self.stx(insn.mode),
             OpCode::STY => self.sty(insn.mode),
+            OpCode::STZ => self.stz(insn.mode),
+            OpCode::TAS => self.tas(insn.mode),
             OpCode::TAX => self.tax(insn.mode),
             OpCode::TAY => self.tay(insn.mode),
             OpCode::TRB => self.trb(insn.mode),
@@ -139,361 +447,854 @@ impl Cpu {
             OpCode::TXA => self.txa(insn.mode),
             OpCode::TXS => self.txs(insn.mode),
             OpCode::TYA => self.tya(insn.mode),
+            OpCode::WAI => self.wai(insn.mode),
         }
     }
-    // TODO: implement the instructions
-    fn adc(&mut self, mode: AddressingMode) {
-        match mode {
-            AddressingMode::Absolute => {
-                let operand = self
-                    .memory
-                    .read_byte(self.memory.read_word(self.registers.pc + 1));
 
-                self.registers.a += operand + (self.registers.get_flag(StatusFlag::Carry) as u8);
-            }
+    /// Resolves the effective address of a memory operand, together with
+    /// whether indexing carried the address into another page.
+    fn effective_address(&mut self, mode: AddressingMode) -> (u16, bool) {
+        let pc = self.registers.pc;
 
+        match mode {
+            AddressingMode::ZeroPage | AddressingMode::ZeroPageRelative => {
+                (self.bus.read(pc.wrapping_add(1)) as u16, false)
+            }
+            AddressingMode::ZeroPageX => {
+                let base = self.bus.read(pc.wrapping_add(1));
+                (base.wrapping_add(self.registers.x) as u16, false)
+            }
+            AddressingMode::ZeroPageY => {
+                let base = self.bus.read(pc.wrapping_add(1));
+                (base.wrapping_add(self.registers.y) as u16, false)
+            }
+            AddressingMode::Absolute => (self.bus.read_word(pc.wrapping_add(1)), false),
             AddressingMode::AbsoluteX => {
-                let specified_addr = self
-                    .memory
-                    .read_byte(self.memory.read_word(self.registers.pc + 1) as u16);
-                let sum_addr = specified_addr + self.registers.x;
-                let operand = self.memory.read_byte(sum_addr as u16);
-
-                self.registers.a += operand + (self.registers.get_flag(StatusFlag::Carry) as u8);
+                let base = self.bus.read_word(pc.wrapping_add(1));
+                Self::index(base, self.registers.x)
             }
-
             AddressingMode::AbsoluteY => {
-                let specified_addr = self
-                    .memory
-                    .read_byte(self.memory.read_word(self.registers.pc + 1) as u16);
-                let sum_addr = specified_addr + self.registers.y;
-                let operand = self.memory.read_byte(sum_addr as u16);
-
-                self.registers.a += operand + (self.registers.get_flag(StatusFlag::Carry) as u8);
+                let base = self.bus.read_word(pc.wrapping_add(1));
+                Self::index(base, self.registers.y)
             }
-
-            AddressingMode::Immediate => {
-                let operand = self.memory.read_byte(self.registers.pc + 1);
-
-                // i dont know if it is right but i dont care
-                self.registers.a += operand + self.registers.get_flag(StatusFlag::Carry) as u8;
+            AddressingMode::AbsoluteIndirect => {
+                let pointer = self.bus.read_word(pc.wrapping_add(1));
+
+                if self.variant.is_cmos() {
+                    (self.bus.read_word(pointer), false)
+                } else {
+                    // the NMOS 6502 does not carry into the high byte of the
+                    // pointer, so JMP ($xxFF) reads its target from $xxFF/$xx00
+                    let lower = self.bus.read(pointer) as u16;
+                    let upper_addr = (pointer & 0xFF00) | (pointer.wrapping_add(1) & 0x00FF);
+                    let upper = self.bus.read(upper_addr) as u16;
+                    (upper << 8 | lower, false)
+                }
+            }
+            AddressingMode::AbsoluteIndexedIndirect => {
+                let base = self.bus.read_word(pc.wrapping_add(1));
+                let pointer = base.wrapping_add(self.registers.x as u16);
+                (self.bus.read_word(pointer), false)
             }
-
             AddressingMode::Indirect => {
-                let address = self.memory.read_byte(self.registers.pc + 1) as u16;
+                let pointer = self.bus.read(pc.wrapping_add(1));
+                (self.read_zero_page_word(pointer), false)
+            }
+            AddressingMode::IndexedIndirectX => {
+                let pointer = self.bus.read(pc.wrapping_add(1));
+                let address = self.read_zero_page_word(pointer.wrapping_add(self.registers.x));
+                (address, false)
+            }
+            AddressingMode::IndirectIndexedY => {
+                let pointer = self.bus.read(pc.wrapping_add(1));
+                let base = self.read_zero_page_word(pointer);
+                Self::index(base, self.registers.y)
+            }
+            _ => unreachable!("Addressing mode {:?} has no effective address", mode),
+        }
+    }
 
-                let operand = self.memory.read_byte(address);
+    fn index(base: u16, index: u8) -> (u16, bool) {
+        let address = base.wrapping_add(index as u16);
+        (address, (base & 0xFF00) != (address & 0xFF00))
+    }
 
-                self.registers.a += operand + self.registers.get_flag(StatusFlag::Carry) as u8;
+    /// Resolves the operand of the instruction at pc for any addressing mode
+    /// that has one.
+    fn resolve(&mut self, mode: AddressingMode) -> Operand {
+        match mode {
+            AddressingMode::Accumulator => Operand {
+                address: None,
+                value: self.registers.a,
+                page_crossed: false,
+            },
+            AddressingMode::Immediate => Operand {
+                address: None,
+                value: self.bus.read(self.registers.pc.wrapping_add(1)),
+                page_crossed: false,
+            },
+            _ => {
+                let (address, page_crossed) = self.effective_address(mode);
+                Operand {
+                    address: Some(address),
+                    value: self.bus.read(address),
+                    page_crossed,
+                }
             }
+        }
+    }
 
-            AddressingMode::IndexedIndirectX => {
-                let specified_addr = self.memory.read_word(self.registers.pc + 1);
+    /// Reads the operand of a load, logic or arithmetic instruction. Indexing
+    /// across a page boundary costs one extra cycle.
+    fn read_operand(&mut self, mode: AddressingMode) -> u8 {
+        let operand = self.resolve(mode);
+
+        if operand.page_crossed {
+            self.penalty_cycles += 1;
+        }
+        operand.value
+    }
 
-                let operand = self
-                    .memory
-                    .read_byte(specified_addr + self.registers.x as u16);
+    /// Pointers stored in the zero page wrap around from $FF to $00.
+    fn read_zero_page_word(&mut self, pointer: u8) -> u16 {
+        let lower = self.bus.read(pointer as u16) as u16;
+        let upper = self.bus.read(pointer.wrapping_add(1) as u16) as u16;
+        upper << 8 | lower
+    }
 
-                self.registers.a += operand + self.registers.get_flag(StatusFlag::Carry) as u8;
+    /// Applies a read-modify-write operation to the accumulator or to memory
+    /// and returns whether indexing crossed a page boundary.
+    ///
+    /// Like the real parts, the NMOS 6502 writes the unmodified value back
+    /// before the result, while the CMOS parts read the operand a second
+    /// time instead. Devices that react to accesses see both.
+    fn modify<F>(&mut self, mode: AddressingMode, op: F) -> bool
+    where
+        F: FnOnce(&mut Self, u8) -> u8,
+    {
+        let operand = self.resolve(mode);
+        let result = op(self, operand.value);
+
+        match operand.address {
+            Some(address) => {
+                if self.variant.is_cmos() {
+                    self.bus.read(address);
+                } else {
+                    self.bus.write(address, operand.value);
+                }
+                self.bus.write(address, result);
             }
+            None => self.registers.a = result,
+        }
+        operand.page_crossed
+    }
 
-            AddressingMode::IndirectIndexedY => {
-                let specified_addr = self.memory.read_word(self.registers.pc + 1);
+    /// Read-modify-write for shifts and rotates. Unlike INC and DEC, the 65C02
+    /// only spends the extra cycle for absolute,X when the page changes. The
+    /// NMOS 6502 always spends it, which its decode table already accounts for.
+    fn shift<F>(&mut self, mode: AddressingMode, op: F)
+    where
+        F: FnOnce(&mut Self, u8) -> u8,
+    {
+        if self.modify(mode, op) && self.variant.is_cmos() {
+            self.penalty_cycles += 1;
+        }
+    }
 
-                let operand = self
-                    .memory
-                    .read_byte(specified_addr + self.registers.y as u16);
+    fn add_with_carry(&mut self, operand: u8) {
+        let a = self.registers.a;
+        let carry = self.registers.get_flag(StatusFlag::Carry) as u16;
+        let sum = a as u16 + operand as u16 + carry;
+        let result = sum as u8;
+
+        self.registers.set_flag(StatusFlag::Carry, sum > 0xFF);
+        self.registers.set_flag(
+            StatusFlag::Overflow,
+            (!(a ^ operand) & (a ^ result) & 0x80) != 0,
+        );
+        self.registers.a = result;
+        self.registers.update_nz_flags(result);
+    }
 
-                self.registers.a += operand + self.registers.get_flag(StatusFlag::Carry) as u8;
-            }
+    /// Decimal mode addition. On the 65C02, N and Z reflect the BCD corrected
+    /// result. The NMOS 6502 takes Z from the binary sum and N from the sum
+    /// before the upper digit is corrected.
+    fn add_decimal(&mut self, operand: u8) {
+        let a = self.registers.a as i16;
+        let b = operand as i16;
+        let carry = self.registers.get_flag(StatusFlag::Carry) as i16;
+
+        let mut lower = (a & 0x0F) + (b & 0x0F) + carry;
+        if lower >= 0x0A {
+            lower = ((lower + 0x06) & 0x0F) + 0x10;
+        }
+        let mut sum = (a & 0xF0) + (b & 0xF0) + lower;
 
-            AddressingMode::ZeroPage => {
-                let operand = self
-                    .memory
-                    .read_byte(self.memory.read_byte(self.registers.pc + 1) as u16);
+        // V is taken from the sum before the upper digit is corrected
+        let signed = (a & 0xF0) as u8 as i8 as i16 + (b & 0xF0) as u8 as i8 as i16 + lower;
+        self.registers
+            .set_flag(StatusFlag::Overflow, !(-128..=127).contains(&signed));
+        let uncorrected = sum as u8;
 
-                self.registers.a += operand + (self.registers.get_flag(StatusFlag::Carry) as u8);
-            }
+        if sum >= 0xA0 {
+            sum += 0x60;
+        }
+        self.registers.set_flag(StatusFlag::Carry, sum >= 0x100);
+        self.registers.a = sum as u8;
 
-            AddressingMode::ZeroPageX => {
-                let specified_addr = self.memory.read_byte(self.registers.pc + 1) as u16;
-                let sum_addr = specified_addr + self.registers.x as u16;
+        if self.variant.is_cmos() {
+            self.registers.update_nz_flags(self.registers.a);
+        } else {
+            let binary = (a + b + carry) as u8;
+            self.registers.set_flag(StatusFlag::Zero, binary == 0);
+            self.registers
+                .set_flag(StatusFlag::Negative, uncorrected & 0x80 != 0);
+        }
+    }
 
-                self.registers.a += self.memory.read_byte(sum_addr)
-                    + (self.registers.get_flag(StatusFlag::Carry) as u8);
+    /// Decimal mode subtraction. C and V are the same as for a binary
+    /// subtraction. The NMOS 6502 corrects each digit separately and leaves N
+    /// and Z as they are for the binary result.
+    fn subtract_decimal(&mut self, operand: u8) {
+        let a = self.registers.a as i16;
+        let b = operand as i16;
+        let borrow = 1 - self.registers.get_flag(StatusFlag::Carry) as i16;
+
+        let mut lower = (a & 0x0F) - (b & 0x0F) - borrow;
+        let difference = if self.variant.is_cmos() {
+            let mut difference = a - b - borrow;
+            if difference < 0 {
+                difference -= 0x60;
+            }
+            if lower < 0 {
+                difference -= 0x06;
+            }
+            difference
+        } else {
+            if lower < 0 {
+                lower = ((lower - 0x06) & 0x0F) - 0x10;
             }
+            let mut difference = (a & 0xF0) - (b & 0xF0) + lower;
+            if difference < 0 {
+                difference -= 0x60;
+            }
+            difference
+        };
 
-            AddressingMode::ZeroPageY => {
-                let specified_addr = self.memory.read_byte(self.registers.pc + 1) as u16;
-                let sum_addr = specified_addr + self.registers.y as u16;
+        self.add_with_carry(!operand);
+        self.registers.a = difference as u8;
+        if self.variant.is_cmos() {
+            self.registers.update_nz_flags(self.registers.a);
+        }
+    }
+
+    fn decimal_mode(&self) -> bool {
+        self.variant.has_decimal_mode() && self.registers.get_flag(StatusFlag::Decimal)
+    }
 
-                self.registers.a += self.memory.read_byte(sum_addr)
-                    + (self.registers.get_flag(StatusFlag::Carry) as u8);
+    /// ADC in either binary or decimal mode.
+    fn add(&mut self, operand: u8) {
+        if self.decimal_mode() {
+            self.add_decimal(operand);
+            // the 65C02 needs an extra cycle to produce valid N and Z flags
+            if self.variant.is_cmos() {
+                self.penalty_cycles += 1;
             }
+        } else {
+            self.add_with_carry(operand);
+        }
+    }
 
-            _ => unreachable!("Invalid addressing mode {:?} for adc", mode),
+    /// SBC in either binary or decimal mode.
+    fn subtract(&mut self, operand: u8) {
+        if self.decimal_mode() {
+            self.subtract_decimal(operand);
+            if self.variant.is_cmos() {
+                self.penalty_cycles += 1;
+            }
+        } else {
+            self.add_with_carry(!operand);
         }
     }
 
+    fn shift_left(&mut self, value: u8) -> u8 {
+        let result = value << 1;
+        self.registers
+            .set_flag(StatusFlag::Carry, value & 0x80 != 0);
+        self.registers.update_nz_flags(result);
+        result
+    }
+
+    fn shift_right(&mut self, value: u8) -> u8 {
+        let result = value >> 1;
+        self.registers
+            .set_flag(StatusFlag::Carry, value & 0x01 != 0);
+        self.registers.update_nz_flags(result);
+        result
+    }
+
+    fn rotate_left(&mut self, value: u8) -> u8 {
+        let carry = self.registers.get_flag(StatusFlag::Carry) as u8;
+        let result = value << 1 | carry;
+        self.registers
+            .set_flag(StatusFlag::Carry, value & 0x80 != 0);
+        self.registers.update_nz_flags(result);
+        result
+    }
+
+    fn rotate_right(&mut self, value: u8) -> u8 {
+        let carry = self.registers.get_flag(StatusFlag::Carry) as u8;
+        let result = value >> 1 | carry << 7;
+        self.registers
+            .set_flag(StatusFlag::Carry, value & 0x01 != 0);
+        self.registers.update_nz_flags(result);
+        result
+    }
+
+    /// Stores `value` ANDed with the high byte of the base address plus one,
+    /// as done by the unstable SHA, SHX, SHY and TAS. When indexing crosses a
+    /// page, that same value also replaces the high byte of the address.
+    fn store_and_high(&mut self, mode: AddressingMode, value: u8) {
+        let (address, page_crossed) = self.effective_address(mode);
+        let index = match mode {
+            AddressingMode::AbsoluteX => self.registers.x,
+            _ => self.registers.y,
+        };
+        let base = address.wrapping_sub(index as u16);
+        let value = value & ((base >> 8) as u8).wrapping_add(1);
+
+        let address = if page_crossed {
+            (value as u16) << 8 | (address & 0x00FF)
+        } else {
+            address
+        };
+        self.bus.write(address, value);
+    }
+
+    fn compare(&mut self, register: u8, operand: u8) {
+        self.registers
+            .set_flag(StatusFlag::Carry, register >= operand);
+        self.registers
+            .update_nz_flags(register.wrapping_sub(operand));
+    }
+
+    fn adc(&mut self, mode: AddressingMode) {
+        let operand = self.read_operand(mode);
+        self.add(operand);
+    }
+
+    fn alr(&mut self, mode: AddressingMode) {
+        let value = self.registers.a & self.read_operand(mode);
+        self.registers.a = self.shift_right(value);
+    }
+
+    fn anc(&mut self, mode: AddressingMode) {
+        self.registers.a &= self.read_operand(mode);
+        self.registers.update_nz_flags(self.registers.a);
+        self.registers
+            .set_flag(StatusFlag::Carry, self.registers.a & 0x80 != 0);
+    }
+
     fn and(&mut self, mode: AddressingMode) {
-        match mode {
-            AddressingMode::Absolute => {
-                let operand = self
-                    .memory
-                    .read_byte(self.memory.read_word(self.registers.pc + 1));
+        self.registers.a &= self.read_operand(mode);
+        self.registers.update_nz_flags(self.registers.a);
+    }
 
-                self.registers.a &= operand;
+    fn ane(&mut self, mode: AddressingMode) {
+        let operand = self.read_operand(mode);
+        self.registers.a = (self.registers.a | self.magic) & self.registers.x & operand;
+        self.registers.update_nz_flags(self.registers.a);
+    }
+
+    fn arr(&mut self, mode: AddressingMode) {
+        let value = self.registers.a & self.read_operand(mode);
+        let carry = self.registers.get_flag(StatusFlag::Carry);
+        let mut result = value >> 1 | (carry as u8) << 7;
+
+        if self.decimal_mode() {
+            // N mirrors the old carry and V a change of bit 6, then each digit
+            // of the rotated value gets a BCD fixup based on the ANDed value
+            self.registers.set_flag(StatusFlag::Negative, carry);
+            self.registers.set_flag(StatusFlag::Zero, result == 0);
+            self.registers
+                .set_flag(StatusFlag::Overflow, (value ^ result) & 0x40 != 0);
+
+            if (value & 0x0F) + (value & 0x01) > 0x05 {
+                result = (result & 0xF0) | (result.wrapping_add(0x06) & 0x0F);
+            }
+            let fixup = (value as u16 & 0xF0) + (value as u16 & 0x10) > 0x50;
+            if fixup {
+                result = result.wrapping_add(0x60);
             }
+            self.registers.set_flag(StatusFlag::Carry, fixup);
+        } else {
+            self.registers.update_nz_flags(result);
+            self.registers
+                .set_flag(StatusFlag::Carry, result & 0x40 != 0);
+            self.registers.set_flag(
+                StatusFlag::Overflow,
+                ((result >> 6) ^ (result >> 5)) & 0x01 != 0,
+            );
+        }
+        self.registers.a = result;
+    }
 
-            AddressingMode::AbsoluteX => {
-                let specified_addr = self
-                    .memory
-                    .read_byte(self.memory.read_word(self.registers.pc + 1) as u16);
-                let sum_addr = specified_addr + self.registers.x;
-                let operand = self.memory.read_byte(sum_addr as u16);
+    fn asl(&mut self, mode: AddressingMode) {
+        self.shift(mode, Self::shift_left);
+    }
 
-                self.registers.a &= operand;
-            }
+    fn bbr(&mut self, mode: AddressingMode, bit: u8) {
+        let value = self.resolve(mode).value;
+        self.branch(mode, (value & 1 << bit) == 0);
+    }
 
-            AddressingMode::AbsoluteY => {
-                let specified_addr = self
-                    .memory
-                    .read_byte(self.memory.read_word(self.registers.pc + 1) as u16);
-                let sum_addr = specified_addr + self.registers.y;
-                let operand = self.memory.read_byte(sum_addr as u16);
+    fn bbs(&mut self, mode: AddressingMode, bit: u8) {
+        let value = self.resolve(mode).value;
+        self.branch(mode, (value & 1 << bit) != 0);
+    }
 
-                self.registers.a += operand;
-            }
+    fn bcc(&mut self, mode: AddressingMode) {
+        let carry = self.registers.get_flag(StatusFlag::Carry);
+        self.branch(mode, !carry);
+    }
 
-            AddressingMode::Immediate => {
-                let operand = self.memory.read_byte(self.registers.pc + 1);
+    fn bcs(&mut self, mode: AddressingMode) {
+        let carry = self.registers.get_flag(StatusFlag::Carry);
+        self.branch(mode, carry);
+    }
 
-                // i dont know if it is right but i dont care
-                self.registers.a &= operand;
-            }
+    fn beq(&mut self, mode: AddressingMode) {
+        let zero = self.registers.get_flag(StatusFlag::Zero);
+        self.branch(mode, zero);
+    }
 
-            AddressingMode::Indirect => {
-                let address = self.memory.read_byte(self.registers.pc + 1) as u16;
+    fn bit(&mut self, mode: AddressingMode) {
+        let operand = self.read_operand(mode);
 
-                let operand = self.memory.read_byte(address);
+        // the immediate form has no memory location to copy N and V from
+        if !matches!(mode, AddressingMode::Immediate) {
+            self.registers
+                .set_flag(StatusFlag::Negative, operand & 0x80 != 0);
+            self.registers
+                .set_flag(StatusFlag::Overflow, operand & 0x40 != 0);
+        }
+        self.registers
+            .set_flag(StatusFlag::Zero, self.registers.a & operand == 0);
+    }
 
-                self.registers.a &= operand;
-            }
+    fn bmi(&mut self, mode: AddressingMode) {
+        let negative = self.registers.get_flag(StatusFlag::Negative);
+        self.branch(mode, negative);
+    }
 
-            AddressingMode::IndexedIndirectX => {
-                let specified_addr = self.memory.read_word(self.registers.pc + 1);
+    fn bne(&mut self, mode: AddressingMode) {
+        let zero = self.registers.get_flag(StatusFlag::Zero);
+        self.branch(mode, !zero);
+    }
 
-                let operand = self
-                    .memory
-                    .read_byte(specified_addr + self.registers.x as u16);
+    fn bpl(&mut self, mode: AddressingMode) {
+        let negative = self.registers.get_flag(StatusFlag::Negative);
+        self.branch(mode, !negative);
+    }
 
-                self.registers.a &= operand;
-            }
+    fn bra(&mut self, mode: AddressingMode) {
+        self.branch(mode, true);
+        // the base cycle count of BRA already covers the taken branch
+        self.penalty_cycles -= 1;
+    }
 
-            AddressingMode::IndirectIndexedY => {
-                let specified_addr = self.memory.read_word(self.registers.pc + 1);
+    fn brk(&mut self, mode: AddressingMode) {
+        // the byte after BRK is a signature byte and is skipped on return
+        let return_addr = self.registers.pc.wrapping_add(2);
+        self.interrupt(return_addr, IRQ, true);
+    }
 
-                let operand = self
-                    .memory
-                    .read_byte(specified_addr + self.registers.y as u16);
+    fn bvc(&mut self, mode: AddressingMode) {
+        let overflow = self.registers.get_flag(StatusFlag::Overflow);
+        self.branch(mode, !overflow);
+    }
 
-                self.registers.a &= operand;
-            }
+    fn bvs(&mut self, mode: AddressingMode) {
+        let overflow = self.registers.get_flag(StatusFlag::Overflow);
+        self.branch(mode, overflow);
+    }
 
-            AddressingMode::ZeroPage => {
-                let operand = self
-                    .memory
-                    .read_byte(self.memory.read_byte(self.registers.pc + 1) as u16);
+    fn clc(&mut self, mode: AddressingMode) {
+        self.registers.set_flag(StatusFlag::Carry, false);
+    }
 
-                self.registers.a &= operand;
-            }
+    fn cld(&mut self, mode: AddressingMode) {
+        self.registers.set_flag(StatusFlag::Decimal, false);
+    }
 
-            AddressingMode::ZeroPageX => {
-                let specified_addr = self.memory.read_byte(self.registers.pc + 1) as u16;
-                let sum_addr = specified_addr + self.registers.x as u16;
+    fn cli(&mut self, mode: AddressingMode) {
+        self.registers.set_flag(StatusFlag::NoInterrupts, false);
+    }
 
-                self.registers.a &= self.memory.read_byte(sum_addr);
-            }
+    fn clv(&mut self, mode: AddressingMode) {
+        self.registers.set_flag(StatusFlag::Overflow, false);
+    }
 
-            AddressingMode::ZeroPageY => {
-                let specified_addr = self.memory.read_byte(self.registers.pc + 1) as u16;
-                let sum_addr = specified_addr + self.registers.y as u16;
+    fn cmp(&mut self, mode: AddressingMode) {
+        let operand = self.read_operand(mode);
+        self.compare(self.registers.a, operand);
+    }
 
-                self.registers.a &= self.memory.read_byte(sum_addr);
-            }
+    fn cpx(&mut self, mode: AddressingMode) {
+        let operand = self.read_operand(mode);
+        self.compare(self.registers.x, operand);
+    }
 
-            _ => unreachable!("Invalid addressing mode {:?} for and", mode),
-        }
+    fn cpy(&mut self, mode: AddressingMode) {
+        let operand = self.read_operand(mode);
+        self.compare(self.registers.y, operand);
     }
 
-    fn asl(&mut self, mode: AddressingMode) {
-        match mode {
-            // it should be Absolute RMW but i dont know the difference
-            AddressingMode::Absolute => {
-                let addr = self.memory.read_word(self.registers.pc + 1);
+    fn dcp(&mut self, mode: AddressingMode) {
+        self.modify(mode, |cpu, value| {
+            let result = value.wrapping_sub(1);
+            cpu.compare(cpu.registers.a, result);
+            result
+        });
+    }
 
-                self.memory
-                    .write_byte(addr, self.memory.read_byte(addr) << 1);
-            }
+    fn dec(&mut self, mode: AddressingMode) {
+        self.modify(mode, |cpu, value| {
+            let result = value.wrapping_sub(1);
+            cpu.registers.update_nz_flags(result);
+            result
+        });
+    }
 
-            AddressingMode::AbsoluteX => {
-                let specified_addr = self.memory.read_word(self.registers.pc + 1) as u16;
-                let sum_addr = specified_addr + self.registers.x as u16;
+    fn dex(&mut self, mode: AddressingMode) {
+        self.registers.x = self.registers.x.wrapping_sub(1);
+        self.registers.update_nz_flags(self.registers.x);
+    }
 
-                self.memory
-                    .write_byte(sum_addr, self.memory.read_byte(sum_addr) << 1);
-            }
+    fn dey(&mut self, mode: AddressingMode) {
+        self.registers.y = self.registers.y.wrapping_sub(1);
+        self.registers.update_nz_flags(self.registers.y);
+    }
 
-            AddressingMode::Accumulator => {
-                self.registers.a <<= 1;
-            }
+    fn eor(&mut self, mode: AddressingMode) {
+        self.registers.a ^= self.read_operand(mode);
+        self.registers.update_nz_flags(self.registers.a);
+    }
 
-            AddressingMode::ZeroPage => {
-                let addr = self.memory.read_byte(self.registers.pc + 1) as u16;
+    fn inc(&mut self, mode: AddressingMode) {
+        self.modify(mode, |cpu, value| {
+            let result = value.wrapping_add(1);
+            cpu.registers.update_nz_flags(result);
+            result
+        });
+    }
 
-                self.memory
-                    .write_byte(addr, self.memory.read_byte(addr) << 1);
-            }
+    fn inx(&mut self, mode: AddressingMode) {
+        self.registers.x = self.registers.x.wrapping_add(1);
+        self.registers.update_nz_flags(self.registers.x);
+    }
 
-            AddressingMode::ZeroPageX => {
-                let addr = (self.memory.read_byte(self.registers.pc + 1) + self.registers.x) as u16;
+    fn iny(&mut self, mode: AddressingMode) {
+        self.registers.y = self.registers.y.wrapping_add(1);
+        self.registers.update_nz_flags(self.registers.y);
+    }
 
-                self.memory
-                    .write_byte(addr, self.memory.read_byte(addr) << 1);
-            }
+    fn isb(&mut self, mode: AddressingMode) {
+        self.modify(mode, |cpu, value| {
+            let result = value.wrapping_add(1);
+            cpu.subtract(result);
+            result
+        });
+    }
 
-            _ => unreachable!("Invalid addressing mode {:?} for asl", mode),
-        }
+    fn jam(&mut self, mode: AddressingMode) {
+        // the processor locks up and only a reset brings it back
+        self.state = State::Stopped;
     }
 
-    fn bbr(&mut self, mode: AddressingMode, bit: u8) {
-        if (self.registers.p & 1 << bit) == 0 {
-            let rel_jmp_addr = self.memory.read_byte(self.registers.pc + 1) as i8;
-            if rel_jmp_addr < 0 {
-                self.registers.pc -= rel_jmp_addr as u16;
-            } else {
-                self.registers.pc += rel_jmp_addr as u16;
-            }
-        }
+    fn jmp(&mut self, mode: AddressingMode) {
+        let (address, _) = self.effective_address(mode);
+        self.registers.pc = address;
     }
 
-    fn bbs(&mut self, mode: AddressingMode, bit: u8) {
-        if (self.registers.p & 1 << bit) != 0 {
-            let rel_jmp_addr = self.memory.read_byte(self.registers.pc + 1) as i8;
-            if rel_jmp_addr < 0 {
-                self.registers.pc -= rel_jmp_addr as u16;
-            } else {
-                self.registers.pc += rel_jmp_addr as u16;
-            }
-        }
+    fn jsr(&mut self, mode: AddressingMode) {
+        // the pushed address points at the last byte of the JSR instruction
+        let return_addr = self.registers.pc.wrapping_add(2);
+
+        self.push_word(return_addr);
+
+        let (address, _) = self.effective_address(mode);
+        self.registers.pc = address;
     }
 
-    fn bcc(&mut self, mode: AddressingMode) {
-        if !self.registers.get_flag(StatusFlag::Carry) {
-            let rel_jmp_addr = self.memory.read_byte(self.registers.pc + 1) as i8;
-            if rel_jmp_addr < 0 {
-                self.registers.pc -= rel_jmp_addr as u16;
-            } else {
-                self.registers.pc += rel_jmp_addr as u16;
-            }
-        }
+    fn las(&mut self, mode: AddressingMode) {
+        let value = self.read_operand(mode) & self.registers.sp;
+        self.registers.a = value;
+        self.registers.x = value;
+        self.registers.sp = value;
+        self.registers.update_nz_flags(value);
     }
 
-    fn bcs(&mut self, mode: AddressingMode) {
-        if self.registers.get_flag(StatusFlag::Carry) {
-            let rel_jmp_addr = self.memory.read_byte(self.registers.pc + 1) as i8;
-            if rel_jmp_addr < 0 {
-                self.registers.pc -= rel_jmp_addr as u16;
-            } else {
-                self.registers.pc += rel_jmp_addr as u16;
-            }
+    fn lax(&mut self, mode: AddressingMode) {
+        let value = self.read_operand(mode);
+        self.registers.a = value;
+        self.registers.x = value;
+        self.registers.update_nz_flags(value);
+    }
+
+    fn lda(&mut self, mode: AddressingMode) {
+        self.registers.a = self.read_operand(mode);
+        self.registers.update_nz_flags(self.registers.a);
+    }
+
+    fn ldx(&mut self, mode: AddressingMode) {
+        self.registers.x = self.read_operand(mode);
+        self.registers.update_nz_flags(self.registers.x);
+    }
+
+    fn ldy(&mut self, mode: AddressingMode) {
+        self.registers.y = self.read_operand(mode);
+        self.registers.update_nz_flags(self.registers.y);
+    }
+
+    fn lsr(&mut self, mode: AddressingMode) {
+        self.shift(mode, Self::shift_right);
+    }
+
+    fn lxa(&mut self, mode: AddressingMode) {
+        let value = (self.registers.a | self.magic) & self.read_operand(mode);
+        self.registers.a = value;
+        self.registers.x = value;
+        self.registers.update_nz_flags(value);
+    }
+
+    fn nop(&mut self, mode: AddressingMode) {
+        // the NMOS multi-byte NOPs read their operand like a load, including
+        // the page crossing penalty, while the 65C02 ones just skip it
+        let reads = !matches!(mode, AddressingMode::Implied | AddressingMode::Immediate);
+        if reads && !self.variant.is_cmos() {
+            self.read_operand(mode);
         }
     }
 
-    fn beq(&mut self, mode: AddressingMode) {}
-
-    fn bit(&mut self, mode: AddressingMode) {}
-
-    fn bmi(&mut self, mode: AddressingMode) {}
-
-    fn bne(&mut self, mode: AddressingMode) {}
-
-    fn bpl(&mut self, mode: AddressingMode) {}
-
-    fn brk(&mut self, mode: AddressingMode) {}
-
-    fn bvc(&mut self, mode: AddressingMode) {}
-
-    fn bvs(&mut self, mode: AddressingMode) {}
-
-    fn clc(&mut self, mode: AddressingMode) {}
-
-    fn cld(&mut self, mode: AddressingMode) {}
-
-    fn cli(&mut self, mode: AddressingMode) {}
-
-    fn clv(&mut self, mode: AddressingMode) {}
-
-    fn cmp(&mut self, mode: AddressingMode) {}
-
-    fn cpx(&mut self, mode: AddressingMode) {}
-
-    fn cpy(&mut self, mode: AddressingMode) {}
-
-    fn dcp(&mut self, mode: AddressingMode) {}
-
-    fn dec(&mut self, mode: AddressingMode) {}
-
-    fn dex(&mut self, mode: AddressingMode) {}
-    fn dey(&mut self, mode: AddressingMode) {}
-    fn eor(&mut self, mode: AddressingMode) {}
-    fn inc(&mut self, mode: AddressingMode) {}
-    fn inx(&mut self, mode: AddressingMode) {}
-    fn iny(&mut self, mode: AddressingMode) {}
-    fn isb(&mut self, mode: AddressingMode) {}
-    fn jmp(&mut self, mode: AddressingMode) {}
-    fn jsr(&mut self, mode: AddressingMode) {}
-    fn lax(&mut self, mode: AddressingMode) {}
-    fn lda(&mut self, mode: AddressingMode) {}
-    fn ldx(&mut self, mode: AddressingMode) {}
-    fn ldy(&mut self, mode: AddressingMode) {}
-    fn lsr(&mut self, mode: AddressingMode) {}
-    fn nop(&mut self, mode: AddressingMode) {}
-    fn ora(&mut self, mode: AddressingMode) {}
-    fn pha(&mut self, mode: AddressingMode) {}
-    fn php(&mut self, mode: AddressingMode) {}
-    fn pla(&mut self, mode: AddressingMode) {}
-    fn plp(&mut self, mode: AddressingMode) {}
-    fn rla(&mut self, mode: AddressingMode) {}
-    fn rmb0(&mut self, mode: AddressingMode) {}
-    fn rmb1(&mut self, mode: AddressingMode) {}
-    fn rmb2(&mut self, mode: AddressingMode) {}
-    fn rmb3(&mut self, mode: AddressingMode) {}
-    fn rmb4(&mut self, mode: AddressingMode) {}
-    fn rmb5(&mut self, mode: AddressingMode) {}
-    fn rmb6(&mut self, mode: AddressingMode) {}
-    fn rmb7(&mut self, mode: AddressingMode) {}
-    fn rol(&mut self, mode: AddressingMode) {}
-    fn ror(&mut self, mode: AddressingMode) {}
-    fn rra(&mut self, mode: AddressingMode) {}
-    fn rti(&mut self, mode: AddressingMode) {}
-    fn rts(&mut self, mode: AddressingMode) {}
-    fn sax(&mut self, mode: AddressingMode) {}
-    fn sbc(&mut self, mode: AddressingMode) {}
-    fn sec(&mut self, mode: AddressingMode) {}
-    fn sed(&mut self, mode: AddressingMode) {}
-    fn sei(&mut self, mode: AddressingMode) {}
-    fn slo(&mut self, mode: AddressingMode) {}
-    fn sre(&mut self, mode: AddressingMode) {}
-    fn sta(&mut self, mode: AddressingMode) {}
-    fn stx(&mut self, mode: AddressingMode) {}
-    fn sty(&mut self, mode: AddressingMode) {}
-    fn tax(&mut self, mode: AddressingMode) {}
-    fn tay(&mut self, mode: AddressingMode) {}
-    fn trb(&mut self, mode: AddressingMode) {}
-    fn tsb(&mut self, mode: AddressingMode) {}
-    fn tsx(&mut self, mode: AddressingMode) {}
-    fn txa(&mut self, mode: AddressingMode) {}
-    fn txs(&mut self, mode: AddressingMode) {}
-    fn tya(&mut self, mode: AddressingMode) {}
+    fn ora(&mut self, mode: AddressingMode) {
+        self.registers.a |= self.read_operand(mode);
+        self.registers.update_nz_flags(self.registers.a);
+    }
+
+    fn pha(&mut self, mode: AddressingMode) {
+        self.push_byte(self.registers.a);
+    }
+
+    fn php(&mut self, mode: AddressingMode) {
+        self.push_status(true);
+    }
+
+    fn phx(&mut self, mode: AddressingMode) {
+        self.push_byte(self.registers.x);
+    }
+
+    fn phy(&mut self, mode: AddressingMode) {
+        self.push_byte(self.registers.y);
+    }
+
+    fn pla(&mut self, mode: AddressingMode) {
+        self.registers.a = self.pull_byte();
+        self.registers.update_nz_flags(self.registers.a);
+    }
+
+    fn plp(&mut self, mode: AddressingMode) {
+        self.pull_status();
+    }
+
+    fn plx(&mut self, mode: AddressingMode) {
+        self.registers.x = self.pull_byte();
+        self.registers.update_nz_flags(self.registers.x);
+    }
+
+    fn ply(&mut self, mode: AddressingMode) {
+        self.registers.y = self.pull_byte();
+        self.registers.update_nz_flags(self.registers.y);
+    }
+
+    fn rla(&mut self, mode: AddressingMode) {
+        self.modify(mode, |cpu, value| {
+            let result = cpu.rotate_left(value);
+            cpu.registers.a &= result;
+            cpu.registers.update_nz_flags(cpu.registers.a);
+            result
+        });
+    }
+
+    fn rmb(&mut self, mode: AddressingMode, bit: u8) {
+        self.modify(mode, |_, value| value & !(1 << bit));
+    }
+
+    fn rol(&mut self, mode: AddressingMode) {
+        self.shift(mode, Self::rotate_left);
+    }
+
+    fn ror(&mut self, mode: AddressingMode) {
+        self.shift(mode, Self::rotate_right);
+    }
+
+    fn rra(&mut self, mode: AddressingMode) {
+        self.modify(mode, |cpu, value| {
+            let result = cpu.rotate_right(value);
+            cpu.add(result);
+            result
+        });
+    }
+
+    fn rti(&mut self, mode: AddressingMode) {
+        self.pull_status();
+        self.registers.pc = self.pull_word();
+    }
+
+    fn rts(&mut self, mode: AddressingMode) {
+        self.registers.pc = self.pull_word().wrapping_add(1);
+    }
+
+    fn sax(&mut self, mode: AddressingMode) {
+        let (address, _) = self.effective_address(mode);
+        self.bus.write(address, self.registers.a & self.registers.x);
+    }
+
+    fn sbc(&mut self, mode: AddressingMode) {
+        let operand = self.read_operand(mode);
+        self.subtract(operand);
+    }
+
+    fn sbx(&mut self, mode: AddressingMode) {
+        let operand = self.read_operand(mode);
+        let value = self.registers.a & self.registers.x;
+        self.registers.set_flag(StatusFlag::Carry, value >= operand);
+        self.registers.x = value.wrapping_sub(operand);
+        self.registers.update_nz_flags(self.registers.x);
+    }
+
+    fn sec(&mut self, mode: AddressingMode) {
+        self.registers.set_flag(StatusFlag::Carry, true);
+    }
+
+    fn sed(&mut self, mode: AddressingMode) {
+        self.registers.set_flag(StatusFlag::Decimal, true);
+    }
+
+    fn sei(&mut self, mode: AddressingMode) {
+        self.registers.set_flag(StatusFlag::NoInterrupts, true);
+    }
+
+    fn sha(&mut self, mode: AddressingMode) {
+        let value = self.registers.a & self.registers.x;
+        self.store_and_high(mode, value);
+    }
+
+    fn shx(&mut self, mode: AddressingMode) {
+        self.store_and_high(mode, self.registers.x);
+    }
+
+    fn shy(&mut self, mode: AddressingMode) {
+        self.store_and_high(mode, self.registers.y);
+    }
+
+    fn slo(&mut self, mode: AddressingMode) {
+        self.modify(mode, |cpu, value| {
+            let result = cpu.shift_left(value);
+            cpu.registers.a |= result;
+            cpu.registers.update_nz_flags(cpu.registers.a);
+            result
+        });
+    }
+
+    fn smb(&mut self, mode: AddressingMode, bit: u8) {
+        self.modify(mode, |_, value| value | 1 << bit);
+    }
+
+    fn sre(&mut self, mode: AddressingMode) {
+        self.modify(mode, |cpu, value| {
+            let result = cpu.shift_right(value);
+            cpu.registers.a ^= result;
+            cpu.registers.update_nz_flags(cpu.registers.a);
+            result
+        });
+    }
+
+    fn sta(&mut self, mode: AddressingMode) {
+        let (address, _) = self.effective_address(mode);
+        self.bus.write(address, self.registers.a);
+    }
+
+    fn stp(&mut self, mode: AddressingMode) {
+        self.state = State::Stopped;
+    }
+
+    fn stx(&mut self, mode: AddressingMode) {
+        let (address, _) = self.effective_address(mode);
+        self.bus.write(address, self.registers.x);
+    }
+
+    fn sty(&mut self, mode: AddressingMode) {
+        let (address, _) = self.effective_address(mode);
+        self.bus.write(address, self.registers.y);
+    }
+
+    fn stz(&mut self, mode: AddressingMode) {
+        let (address, _) = self.effective_address(mode);
+        self.bus.write(address, 0);
+    }
+
+    fn tas(&mut self, mode: AddressingMode) {
+        self.registers.sp = self.registers.a & self.registers.x;
+        self.store_and_high(mode, self.registers.sp);
+    }
+
+    fn tax(&mut self, mode: AddressingMode) {
+        self.registers.x = self.registers.a;
+        self.registers.update_nz_flags(self.registers.x);
+    }
+
+    fn tay(&mut self, mode: AddressingMode) {
+        self.registers.y = self.registers.a;
+        self.registers.update_nz_flags(self.registers.y);
+    }
+
+    fn trb(&mut self, mode: AddressingMode) {
+        self.modify(mode, |cpu, value| {
+            cpu.registers
+                .set_flag(StatusFlag::Zero, cpu.registers.a & value == 0);
+            value & !cpu.registers.a
+        });
+    }
+
+    fn tsb(&mut self, mode: AddressingMode) {
+        self.modify(mode, |cpu, value| {
+            cpu.registers
+                .set_flag(StatusFlag::Zero, cpu.registers.a & value == 0);
+            value | cpu.registers.a
+        });
+    }
+
+    fn tsx(&mut self, mode: AddressingMode) {
+        self.registers.x = self.registers.sp;
+        self.registers.update_nz_flags(self.registers.x);
+    }
+
+    fn txa(&mut self, mode: AddressingMode) {
+        self.registers.a = self.registers.x;
+        self.registers.update_nz_flags(self.registers.a);
+    }
+
+    fn txs(&mut self, mode: AddressingMode) {
+        self.registers.sp = self.registers.x;
+    }
+
+    fn tya(&mut self, mode: AddressingMode) {
+        self.registers.a = self.registers.y;
+        self.registers.update_nz_flags(self.registers.a);
+    }
+
+    fn wai(&mut self, mode: AddressingMode) {
+        self.state = State::Waiting;
+    }
 }
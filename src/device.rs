@@ -0,0 +1,182 @@
+//! Memory-mapped I/O devices and the interrupt lines they drive
+
+use std::cell::RefCell;
+use std::ops::RangeInclusive;
+use std::rc::Rc;
+
+/// What a device sees of the system during an access: the time and its own
+/// interrupt outputs.
+pub struct Context {
+    cycles: u64,
+    irq: bool,
+    nmi: bool,
+    exit: Option<u8>,
+}
+
+impl Context {
+    /// CPU cycles elapsed up to the start of the current instruction.
+    pub fn cycles(&self) -> u64 {
+        self.cycles
+    }
+
+    /// Drives the IRQ output of the device. IRQ is level triggered, so it
+    /// stays asserted until the device releases it again.
+    pub fn set_irq(&mut self, active: bool) {
+        self.irq = active;
+    }
+
+    pub fn irq(&self) -> bool {
+        self.irq
+    }
+
+    /// Pulls NMI low for an edge, which the CPU services exactly once.
+    pub fn trigger_nmi(&mut self) {
+        self.nmi = true;
+    }
+
+    /// Asks the emulator to stop running, with `code` as the exit status.
+    pub fn request_exit(&mut self, code: u8) {
+        self.exit = Some(code);
+    }
+}
+
+/// A peripheral that handles accesses to the address range it is attached
+/// to. Offsets are relative to the start of that range.
+pub trait Device {
+    /// Reads a register. Unlike `peek`, reads may have side effects, such as
+    /// clearing a flag.
+    fn read(&mut self, offset: u16, context: &mut Context) -> u8;
+
+    fn write(&mut self, offset: u16, value: u8, context: &mut Context);
+
+    /// Reads a register without any side effects, for debuggers.
+    fn peek(&self, offset: u16) -> u8;
+
+    /// Called after every CPU step, so the device can catch up with time,
+    /// like counting down a timer.
+    fn tick(&mut self, context: &mut Context) {}
+}
+
+/// Lets the host keep a handle on a device after attaching it, for instance
+/// to drive its input pins.
+impl<D: Device> Device for Rc<RefCell<D>> {
+    fn read(&mut self, offset: u16, context: &mut Context) -> u8 {
+        self.borrow_mut().read(offset, context)
+    }
+
+    fn write(&mut self, offset: u16, value: u8, context: &mut Context) {
+        self.borrow_mut().write(offset, value, context);
+    }
+
+    fn peek(&self, offset: u16) -> u8 {
+        self.borrow().peek(offset)
+    }
+
+    fn tick(&mut self, context: &mut Context) {
+        self.borrow_mut().tick(context);
+    }
+}
+
+struct Attached {
+    range: RangeInclusive<u16>,
+    device: Box<dyn Device>,
+    irq: bool,
+    nmi: bool,
+}
+
+/// The devices attached to a bus, along with the state of their interrupt
+/// outputs. The IRQ outputs are wire-ORed together like on a real board.
+#[derive(Default)]
+pub struct Devices {
+    attached: Vec<Attached>,
+    cycles: u64,
+    exit: Option<u8>,
+}
+
+impl Devices {
+    pub fn new() -> Devices {
+        Devices::default()
+    }
+
+    /// Attaches `device` to `range` and returns its index. When ranges
+    /// overlap, the device attached last wins.
+    pub fn attach<D: Device + 'static>(&mut self, range: RangeInclusive<u16>, device: D) -> usize {
+        self.attached.push(Attached {
+            range,
+            device: Box::new(device),
+            irq: false,
+            nmi: false,
+        });
+        self.attached.len() - 1
+    }
+
+    /// Index of the device handling `address`, if any.
+    pub fn find(&self, address: u16) -> Option<usize> {
+        self.attached
+            .iter()
+            .rposition(|attached| attached.range.contains(&address))
+    }
+
+    pub fn read(&mut self, index: usize, address: u16) -> u8 {
+        let offset = address - self.attached[index].range.start();
+        self.access(index, |device, context| device.read(offset, context))
+    }
+
+    pub fn write(&mut self, index: usize, address: u16, value: u8) {
+        let offset = address - self.attached[index].range.start();
+        self.access(index, |device, context| {
+            device.write(offset, value, context)
+        });
+    }
+
+    pub fn peek(&self, index: usize, address: u16) -> u8 {
+        let attached = &self.attached[index];
+        attached.device.peek(address - attached.range.start())
+    }
+
+    /// Advances the devices to `cycles`.
+    pub fn sync(&mut self, cycles: u64) {
+        self.cycles = cycles;
+        for index in 0..self.attached.len() {
+            self.access(index, |device, context| device.tick(context));
+        }
+    }
+
+    /// Whether any device asserts IRQ.
+    pub fn irq(&self) -> bool {
+        self.attached.iter().any(|attached| attached.irq)
+    }
+
+    /// Whether any device triggered NMI since the last call.
+    pub fn take_nmi(&mut self) -> bool {
+        let mut nmi = false;
+        for attached in self.attached.iter_mut() {
+            nmi |= std::mem::replace(&mut attached.nmi, false);
+        }
+        nmi
+    }
+
+    /// Exit status requested by a device, if any.
+    pub fn exit_code(&self) -> Option<u8> {
+        self.exit
+    }
+
+    fn access<T, F>(&mut self, index: usize, op: F) -> T
+    where
+        F: FnOnce(&mut dyn Device, &mut Context) -> T,
+    {
+        let attached = &mut self.attached[index];
+        let mut context = Context {
+            cycles: self.cycles,
+            irq: attached.irq,
+            nmi: false,
+            exit: None,
+        };
+        let result = op(attached.device.as_mut(), &mut context);
+
+        attached.irq = context.irq;
+        attached.nmi |= context.nmi;
+        self.exit = self.exit.or(context.exit);
+        result
+    }
+}
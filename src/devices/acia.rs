@@ -0,0 +1,158 @@
+//! The W65C51 Asynchronous Communications Interface Adapter, a UART
+
+use super::serial::Serial;
+use crate::device::{Context, Device};
+
+// register offsets, the ACIA decodes the low two address lines
+pub const DATA: u16 = 0x0;
+/// Status on reads, a programmed reset on writes.
+pub const STATUS: u16 = 0x1;
+pub const COMMAND: u16 = 0x2;
+pub const CONTROL: u16 = 0x3;
+
+/// Bits of the status register.
+#[derive(Copy, Debug, Clone)]
+pub enum Status {
+    ParityError = 1 << 0,
+    FramingError = 1 << 1,
+    Overrun = 1 << 2,
+    ReceiverFull = 1 << 3,
+    TransmitterEmpty = 1 << 4,
+    /// Data carrier detect, active low.
+    Dcd = 1 << 5,
+    /// Data set ready, active low.
+    Dsr = 1 << 6,
+    /// Set when the receiver fills up or the transmitter empties, cleared by
+    /// reading the status, even if the receiver is still full.
+    Interrupt = 1 << 7,
+}
+
+// command register: DTR enables the receiver, IRD masks its interrupt and
+// TIC = 01 enables the transmitter interrupt
+const DTR: u8 = 1 << 0;
+const IRD: u8 = 1 << 1;
+const TIC_MASK: u8 = 0b1100;
+const TIC_IRQ: u8 = 0b0100;
+const ECHO: u8 = 1 << 4;
+
+/// A W65C51 wired to a host serial line. Characters are sent right away,
+/// regardless of the baud rate set in the control register.
+pub struct Acia {
+    serial: Box<dyn Serial>,
+    rx_data: u8,
+    tx_data: Option<u8>,
+    status: u8,
+    command: u8,
+    control: u8,
+}
+
+impl Acia {
+    pub fn new<S: Serial + 'static>(serial: S) -> Acia {
+        Acia {
+            serial: Box::new(serial),
+            rx_data: 0,
+            tx_data: None,
+            status: Status::TransmitterEmpty as u8,
+            command: 0,
+            control: 0,
+        }
+    }
+
+    fn has_status(&self, status: Status) -> bool {
+        self.status & status as u8 != 0
+    }
+
+    fn receiver_enabled(&self) -> bool {
+        self.command & DTR != 0
+    }
+
+    fn transmit(&mut self) {
+        if let Some(byte) = self.tx_data.take() {
+            self.serial.send(byte);
+            self.status |= Status::TransmitterEmpty as u8;
+            if self.command & TIC_MASK == TIC_IRQ {
+                self.status |= Status::Interrupt as u8;
+            }
+        }
+    }
+
+    fn receive(&mut self) {
+        if !self.receiver_enabled() || self.has_status(Status::ReceiverFull) {
+            return;
+        }
+        if let Some(byte) = self.serial.receive() {
+            self.rx_data = byte;
+            self.status |= Status::ReceiverFull as u8;
+            if self.command & IRD == 0 {
+                self.status |= Status::Interrupt as u8;
+            }
+            if self.command & ECHO != 0 {
+                self.serial.send(byte);
+            }
+        }
+    }
+
+    /// Resets the chip as by its RESB pin.
+    pub fn reset(&mut self) {
+        self.tx_data = None;
+        self.status = Status::TransmitterEmpty as u8;
+        self.command = 0;
+        self.control = 0;
+    }
+}
+
+impl Device for Acia {
+    fn read(&mut self, offset: u16, context: &mut Context) -> u8 {
+        let value = match offset & 0x03 {
+            DATA => {
+                self.status &= !(Status::ReceiverFull as u8 | Status::Overrun as u8);
+                self.rx_data
+            }
+            STATUS => {
+                // reading the status acknowledges the interrupt
+                let status = self.status;
+                self.status &= !(Status::Interrupt as u8);
+                status
+            }
+            COMMAND => self.command,
+            _ => self.control,
+        };
+
+        context.set_irq(self.has_status(Status::Interrupt));
+        value
+    }
+
+    fn write(&mut self, offset: u16, value: u8, context: &mut Context) {
+        match offset & 0x03 {
+            DATA => {
+                self.tx_data = Some(value);
+                self.status &= !(Status::TransmitterEmpty as u8);
+            }
+            STATUS => {
+                // programmed reset, which keeps the parity mode
+                self.command &= 0xE0;
+                self.status &= !(Status::Overrun as u8);
+            }
+            COMMAND => self.command = value,
+            _ => self.control = value,
+        }
+
+        context.set_irq(self.has_status(Status::Interrupt));
+    }
+
+    fn peek(&self, offset: u16) -> u8 {
+        match offset & 0x03 {
+            DATA => self.rx_data,
+            STATUS => self.status,
+            COMMAND => self.command,
+            _ => self.control,
+        }
+    }
+
+    fn tick(&mut self, context: &mut Context) {
+        self.transmit();
+        self.receive();
+
+        context.set_irq(self.has_status(Status::Interrupt));
+    }
+}
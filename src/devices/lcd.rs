@@ -0,0 +1,476 @@
+//! The Hitachi HD44780 character LCD controller, mapped on the bus or wired
+//! to the pins of a VIA
+
+use super::via::{Port, Via};
+use crate::device::{Context, Device};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+const DDRAM_SIZE: usize = 80;
+const CGRAM_SIZE: usize = 64;
+// characters per line in two-line mode
+const LINE_LENGTH: u8 = 40;
+const SECOND_LINE: u8 = 0x40;
+
+// execution times in microseconds
+const CLEAR_TIME: u64 = 1520;
+const INSTRUCTION_TIME: u64 = 37;
+
+/// Which port of the VIA a pin belongs to.
+#[derive(Copy, Debug, Clone, PartialEq, Eq)]
+pub enum ViaPort {
+    A = 0,
+    B = 1,
+}
+
+#[derive(Copy, Debug, Clone, PartialEq, Eq)]
+pub struct Pin {
+    pub port: ViaPort,
+    pub bit: u8,
+}
+
+/// How an LCD is wired to a VIA. The data lines are consecutive pins
+/// starting at `data`: DB0-DB7 with eight lines, or DB4-DB7 with four.
+#[derive(Copy, Debug, Clone)]
+pub struct Wiring {
+    pub data: Pin,
+    pub data_lines: u8,
+    pub rs: Pin,
+    pub rw: Pin,
+    pub e: Pin,
+}
+
+impl Wiring {
+    /// The usual breadboard computer wiring: DB0-DB7 on port B and E, RW,
+    /// RS on PA7, PA6 and PA5.
+    pub fn eight_bit() -> Wiring {
+        Wiring {
+            data: Pin {
+                port: ViaPort::B,
+                bit: 0,
+            },
+            data_lines: 8,
+            rs: Pin {
+                port: ViaPort::A,
+                bit: 5,
+            },
+            rw: Pin {
+                port: ViaPort::A,
+                bit: 6,
+            },
+            e: Pin {
+                port: ViaPort::A,
+                bit: 7,
+            },
+        }
+    }
+
+    /// Everything on port B: DB4-DB7 on PB0-PB3, then RS, RW and E on PB4,
+    /// PB5 and PB6.
+    pub fn four_bit() -> Wiring {
+        let pin = |bit| Pin {
+            port: ViaPort::B,
+            bit,
+        };
+        Wiring {
+            data: pin(0),
+            data_lines: 4,
+            rs: pin(4),
+            rw: pin(5),
+            e: pin(6),
+        }
+    }
+
+    fn data_mask(&self) -> u8 {
+        (((1u16 << self.data_lines) - 1) << self.data.bit) as u8
+    }
+}
+
+pub struct Hd44780 {
+    columns: u8,
+    rows: u8,
+    ddram: [u8; DDRAM_SIZE],
+    cgram: [u8; CGRAM_SIZE],
+    // address counter, pointing into CGRAM after "set CGRAM address"
+    address: u8,
+    cgram_selected: bool,
+    increment: bool,
+    shift_on_write: bool,
+    display_on: bool,
+    cursor_on: bool,
+    blink_on: bool,
+    eight_bit: bool,
+    two_lines: bool,
+    // how far the display has been shifted to the left
+    shift: u8,
+    busy_until: u64,
+    cycles_per_microsecond: u64,
+    // first half of a transfer on the 4-bit interface
+    nibble: Option<u8>,
+    read_low_nibble: bool,
+    // levels on the pins of VIA ports A and B, when wired to one
+    pins: [u8; 2],
+    read_value: Option<u8>,
+}
+
+impl Hd44780 {
+    /// Creates a display with `columns` characters on each of its `rows`,
+    /// in the state of the internal reset: 8-bit interface, one line and
+    /// the display off.
+    pub fn new(columns: u8, rows: u8) -> Hd44780 {
+        Hd44780 {
+            columns,
+            rows,
+            ddram: [b' '; DDRAM_SIZE],
+            cgram: [0; CGRAM_SIZE],
+            address: 0,
+            cgram_selected: false,
+            increment: true,
+            shift_on_write: false,
+            display_on: false,
+            cursor_on: false,
+            blink_on: false,
+            eight_bit: true,
+            two_lines: false,
+            shift: 0,
+            busy_until: 0,
+            cycles_per_microsecond: 1,
+            nibble: None,
+            read_low_nibble: false,
+            // the VIA comes out of reset with all pins floating high
+            pins: [0xFF; 2],
+            read_value: None,
+        }
+    }
+
+    /// Sets the CPU clock the busy times are counted in, 1 MHz by default.
+    pub fn set_clock_mhz(&mut self, mhz: u64) {
+        self.cycles_per_microsecond = mhz;
+    }
+
+    /// Wires `lcd` to the pins of `via`. Ports that none of the LCD pins are
+    /// on stay untouched.
+    pub fn connect(lcd: &Rc<RefCell<Hd44780>>, via: &mut Via, wiring: Wiring) {
+        let pins = [wiring.data, wiring.rs, wiring.rw, wiring.e];
+        let uses = |port| pins.iter().any(|pin| pin.port == port);
+
+        if uses(ViaPort::A) {
+            via.connect_port_a(LcdPort {
+                lcd: lcd.clone(),
+                port: ViaPort::A,
+                wiring,
+            });
+        }
+        if uses(ViaPort::B) {
+            via.connect_port_b(LcdPort {
+                lcd: lcd.clone(),
+                port: ViaPort::B,
+                wiring,
+            });
+        }
+    }
+
+    /// The visible characters, one line of text per row. CGRAM characters
+    /// show up as their code point in the Unicode Private Use Area, other
+    /// codes without an ASCII equivalent as '?'.
+    pub fn text(&self) -> String {
+        let mut lines = Vec::new();
+
+        for row in 0..self.rows {
+            // rows 3 and 4 of 4-line displays continue rows 1 and 2
+            let line = row % 2;
+            let offset = (row / 2) * self.columns;
+
+            let text: String = (0..self.columns)
+                .map(|column| {
+                    if !self.display_on || (line == 1 && !self.two_lines) {
+                        return ' ';
+                    }
+                    let position = self.shift as u16 + offset as u16 + column as u16;
+                    let address = if self.two_lines {
+                        line * SECOND_LINE + (position % LINE_LENGTH as u16) as u8
+                    } else {
+                        (position % DDRAM_SIZE as u16) as u8
+                    };
+                    Hd44780::character(self.ddram[self.ddram_index(address)])
+                })
+                .collect();
+            lines.push(text);
+        }
+        lines.join("\n")
+    }
+
+    /// Position of the cursor as (column, row) of DDRAM, ignoring the shift.
+    pub fn cursor(&self) -> (u8, u8) {
+        if self.two_lines {
+            (self.address % SECOND_LINE, self.address / SECOND_LINE)
+        } else {
+            (self.address, 0)
+        }
+    }
+
+    pub fn is_busy(&self, cycles: u64) -> bool {
+        cycles < self.busy_until
+    }
+
+    /// A write on the bus interface, `rs` selecting the data register.
+    pub fn write(&mut self, rs: bool, value: u8, cycles: u64) {
+        if self.eight_bit {
+            self.execute(rs, value, cycles);
+            return;
+        }
+
+        // the 4-bit interface only uses DB4-DB7, high nibble first
+        match self.nibble.take() {
+            Some(high) => self.execute(rs, high | value >> 4, cycles),
+            None => self.nibble = Some(value & 0xF0),
+        }
+    }
+
+    /// A read on the bus interface: the busy flag and address counter, or
+    /// data with `rs` set.
+    pub fn read(&mut self, rs: bool, cycles: u64) -> u8 {
+        if self.eight_bit {
+            return self.read_register(rs, cycles);
+        }
+
+        // both halves come from the same register read
+        self.read_low_nibble = !self.read_low_nibble;
+        if self.read_low_nibble {
+            let value = self.peek_register(rs, cycles);
+            value & 0xF0
+        } else {
+            self.read_register(rs, cycles) << 4
+        }
+    }
+
+    fn peek_register(&self, rs: bool, cycles: u64) -> u8 {
+        if rs {
+            self.read_memory()
+        } else {
+            (self.is_busy(cycles) as u8) << 7 | (self.address & 0x7F)
+        }
+    }
+
+    fn read_register(&mut self, rs: bool, cycles: u64) -> u8 {
+        let value = self.peek_register(rs, cycles);
+        if rs {
+            self.advance_address();
+        }
+        value
+    }
+
+    fn read_memory(&self) -> u8 {
+        if self.cgram_selected {
+            self.cgram[(self.address & 0x3F) as usize]
+        } else {
+            self.ddram[self.ddram_index(self.address)]
+        }
+    }
+
+    fn ddram_index(&self, address: u8) -> usize {
+        if self.two_lines && address >= SECOND_LINE {
+            (address - SECOND_LINE + LINE_LENGTH) as usize
+        } else {
+            address as usize
+        }
+    }
+
+    fn character(code: u8) -> char {
+        match code {
+            0x00..=0x0F => std::char::from_u32(0xE000 + (code & 0x07) as u32).unwrap(),
+            // the A00 character ROM puts a yen sign where ASCII has a backslash
+            0x5C => '¥',
+            0x7E => '→',
+            0x7F => '←',
+            0xDF => '°',
+            0x20..=0x7D => code as char,
+            _ => '?',
+        }
+    }
+
+    /// Moves the address counter in the direction set by the entry mode,
+    /// wrapping around within the memory it points to.
+    fn advance_address(&mut self) {
+        self.address = if self.cgram_selected {
+            let address = if self.increment {
+                self.address.wrapping_add(1)
+            } else {
+                self.address.wrapping_sub(1)
+            };
+            address & 0x3F
+        } else {
+            self.step_ddram_address(self.increment)
+        };
+    }
+
+    fn step_ddram_address(&self, forward: bool) -> u8 {
+        let address = self.address;
+
+        if self.two_lines {
+            match (forward, address) {
+                (true, 0x27) => SECOND_LINE,
+                (true, 0x67) => 0x00,
+                (false, 0x00) => 0x67,
+                (false, 0x40) => 0x27,
+                (true, _) => address + 1,
+                (false, _) => address - 1,
+            }
+        } else {
+            match (forward, address) {
+                (true, 0x4F) => 0x00,
+                (false, 0x00) => 0x4F,
+                (true, _) => address + 1,
+                (false, _) => address - 1,
+            }
+        }
+    }
+
+    fn shift_display(&mut self, right: bool) {
+        let length = if self.two_lines {
+            LINE_LENGTH
+        } else {
+            DDRAM_SIZE as u8
+        };
+        // shifting the display right moves the text window to the left
+        self.shift = if right {
+            (self.shift + length - 1) % length
+        } else {
+            (self.shift + 1) % length
+        };
+    }
+
+    /// Runs an instruction, or a data transfer with `rs` set. Instructions
+    /// received while busy are executed all the same.
+    fn execute(&mut self, rs: bool, value: u8, cycles: u64) {
+        let mut time = INSTRUCTION_TIME;
+
+        if rs {
+            if self.cgram_selected {
+                self.cgram[(self.address & 0x3F) as usize] = value;
+            } else {
+                let index = self.ddram_index(self.address);
+                self.ddram[index] = value;
+                if self.shift_on_write {
+                    self.shift_display(!self.increment);
+                }
+            }
+            self.advance_address();
+        } else if value & 0x80 != 0 {
+            self.address = value & 0x7F;
+            self.cgram_selected = false;
+        } else if value & 0x40 != 0 {
+            self.address = value & 0x3F;
+            self.cgram_selected = true;
+        } else if value & 0x20 != 0 {
+            self.eight_bit = value & 0x10 != 0;
+            self.two_lines = value & 0x08 != 0;
+            self.nibble = None;
+        } else if value & 0x10 != 0 {
+            let right = value & 0x04 != 0;
+            if value & 0x08 != 0 {
+                self.shift_display(right);
+            } else {
+                self.address = self.step_ddram_address(right);
+            }
+        } else if value & 0x08 != 0 {
+            self.display_on = value & 0x04 != 0;
+            self.cursor_on = value & 0x02 != 0;
+            self.blink_on = value & 0x01 != 0;
+        } else if value & 0x04 != 0 {
+            self.increment = value & 0x02 != 0;
+            self.shift_on_write = value & 0x01 != 0;
+        } else if value & 0x02 != 0 {
+            self.address = 0;
+            self.cgram_selected = false;
+            self.shift = 0;
+            time = CLEAR_TIME;
+        } else if value & 0x01 != 0 {
+            self.ddram = [b' '; DDRAM_SIZE];
+            self.address = 0;
+            self.cgram_selected = false;
+            self.shift = 0;
+            self.increment = true;
+            time = CLEAR_TIME;
+        }
+
+        self.busy_until = cycles + time * self.cycles_per_microsecond;
+    }
+
+    fn level(&self, pin: Pin) -> bool {
+        self.pins[pin.port as usize] & 1 << pin.bit != 0
+    }
+
+    /// Follows the pins of one VIA port. RS, RW and the data lines are
+    /// sampled while E is high: reads drive the data lines from the rising
+    /// edge of E, writes are latched on its falling edge.
+    fn update_pins(&mut self, port: ViaPort, pins: u8, wiring: Wiring, cycles: u64) {
+        let enabled = self.level(wiring.e);
+        let rs = self.level(wiring.rs);
+        let reading = self.level(wiring.rw);
+        let data = self.level_data(wiring);
+        self.pins[port as usize] = pins;
+
+        match (enabled, self.level(wiring.e)) {
+            (false, true) if self.level(wiring.rw) => {
+                let rs = self.level(wiring.rs);
+                self.read_value = Some(self.read(rs, cycles));
+            }
+            (true, false) if !reading => self.write(rs, data, cycles),
+            _ => {}
+        }
+        if !self.level(wiring.e) || !self.level(wiring.rw) {
+            self.read_value = None;
+        }
+    }
+
+    /// Value on the data lines as seen by the LCD, aligned to DB7.
+    fn level_data(&self, wiring: Wiring) -> u8 {
+        let data = (self.pins[wiring.data.port as usize] & wiring.data_mask()) >> wiring.data.bit;
+        data << (8 - wiring.data_lines)
+    }
+}
+
+impl Device for Hd44780 {
+    /// Offset 0 is the instruction register, offset 1 the data register.
+    fn read(&mut self, offset: u16, context: &mut Context) -> u8 {
+        Hd44780::read(self, offset & 0x01 != 0, context.cycles())
+    }
+
+    fn write(&mut self, offset: u16, value: u8, context: &mut Context) {
+        Hd44780::write(self, offset & 0x01 != 0, value, context.cycles());
+    }
+
+    fn peek(&self, offset: u16) -> u8 {
+        self.peek_register(offset & 0x01 != 0, 0)
+    }
+}
+
+/// One VIA port with LCD pins on it.
+struct LcdPort {
+    lcd: Rc<RefCell<Hd44780>>,
+    port: ViaPort,
+    wiring: Wiring,
+}
+
+impl Port for LcdPort {
+    fn write(&mut self, pins: u8, ddr: u8, cycles: u64) {
+        // pins the VIA does not drive float high
+        let pins = pins | !ddr;
+        self.lcd
+            .borrow_mut()
+            .update_pins(self.port, pins, self.wiring, cycles);
+    }
+
+    fn read(&self, cycles: u64) -> u8 {
+        let lcd = self.lcd.borrow();
+        match lcd.read_value {
+            Some(value) if self.wiring.data.port == self.port => {
+                let data = value >> (8 - self.wiring.data_lines) << self.wiring.data.bit;
+                let mask = self.wiring.data_mask();
+                (data & mask) | !mask
+            }
+            _ => 0xFF,
+        }
+    }
+}
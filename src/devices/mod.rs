@@ -0,0 +1,6 @@
+//! Peripherals that can be attached to a bus
+pub mod acia;
+pub mod lcd;
+pub mod semihost;
+pub mod serial;
+pub mod via;
@@ -0,0 +1,132 @@
+//! Semihosting: I/O ports that give programs running in the emulator access
+//! to the host's standard streams, exit status and clock
+
+use crate::device::{Context, Device};
+use std::io::{self, Read, Write};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// register offsets
+/// Writes a byte to stdout.
+pub const STDOUT: u16 = 0x00;
+/// Writes a byte to stderr.
+pub const STDERR: u16 = 0x01;
+/// Reads a byte from stdin, blocking until one is available.
+pub const STDIN: u16 = 0x02;
+/// Reads the `Status` bits.
+pub const STATUS: u16 = 0x03;
+/// Writing ends the run, with the value written as exit status.
+pub const EXIT: u16 = 0x04;
+/// Host time in milliseconds since the Unix epoch, 64 bits little endian.
+pub const TIME: u16 = 0x08;
+/// CPU cycles since power on, 64 bits little endian.
+pub const CYCLES: u16 = 0x10;
+
+/// Number of addresses the registers take up.
+pub const SIZE: u16 = 0x18;
+
+/// Bits of the status register.
+#[derive(Copy, Debug, Clone)]
+pub enum Status {
+    /// The last read from `STDIN` hit the end of the input and returned 0.
+    EndOfInput = 1 << 0,
+}
+
+/// The semihosting ports. Reading the low byte of `TIME` or `CYCLES`
+/// latches the whole counter, so that its bytes can be read one by one
+/// without it changing in between.
+pub struct Semihost {
+    status: u8,
+    time: u64,
+    cycles: u64,
+    stdin: Box<dyn Read>,
+    stdout: Box<dyn Write>,
+    stderr: Box<dyn Write>,
+}
+
+impl Default for Semihost {
+    fn default() -> Semihost {
+        Semihost::with_streams(io::stdin(), io::stdout(), io::stderr())
+    }
+}
+
+impl Semihost {
+    /// Connects the ports to the standard streams of the host.
+    pub fn new() -> Semihost {
+        Semihost::default()
+    }
+
+    /// Connects the ports to other streams, to capture the output of a
+    /// program for instance.
+    pub fn with_streams<R, W, E>(stdin: R, stdout: W, stderr: E) -> Semihost
+    where
+        R: Read + 'static,
+        W: Write + 'static,
+        E: Write + 'static,
+    {
+        Semihost {
+            status: 0,
+            time: 0,
+            cycles: 0,
+            stdin: Box::new(stdin),
+            stdout: Box::new(stdout),
+            stderr: Box::new(stderr),
+        }
+    }
+
+    fn read_stdin(&mut self) -> u8 {
+        let mut byte = [0];
+        match self.stdin.read(&mut byte) {
+            Ok(1) => {
+                self.status &= !(Status::EndOfInput as u8);
+                byte[0]
+            }
+            _ => {
+                self.status |= Status::EndOfInput as u8;
+                0
+            }
+        }
+    }
+
+    fn host_time() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |time| time.as_millis() as u64)
+    }
+}
+
+impl Device for Semihost {
+    fn read(&mut self, offset: u16, context: &mut Context) -> u8 {
+        match offset {
+            STDIN => return self.read_stdin(),
+            TIME => self.time = Semihost::host_time(),
+            CYCLES => self.cycles = context.cycles(),
+            _ => {}
+        }
+        self.peek(offset)
+    }
+
+    fn write(&mut self, offset: u16, value: u8, context: &mut Context) {
+        // a closed stream just drops the output, like an unplugged terminal
+        let _ = match offset {
+            STDOUT => self
+                .stdout
+                .write_all(&[value])
+                .and_then(|()| self.stdout.flush()),
+            STDERR => self.stderr.write_all(&[value]),
+            EXIT => {
+                context.request_exit(value);
+                Ok(())
+            }
+            _ => Ok(()),
+        };
+    }
+
+    fn peek(&self, offset: u16) -> u8 {
+        match offset {
+            STATUS => self.status,
+            TIME..=0x0F => (self.time >> ((offset - TIME) * 8)) as u8,
+            CYCLES..=0x17 => (self.cycles >> ((offset - CYCLES) * 8)) as u8,
+            _ => 0,
+        }
+    }
+}
@@ -0,0 +1,285 @@
+//! Host side ends of a serial line: the terminal, a pseudo-terminal or a
+//! TCP socket
+
+use std::io::{self, Read, Write};
+use std::net::{Ipv4Addr, TcpListener};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+
+/// The far end of a serial line, as seen by a UART.
+pub trait Serial {
+    fn send(&mut self, byte: u8);
+
+    /// Next received byte, without blocking.
+    fn receive(&mut self) -> Option<u8>;
+}
+
+/// A serial line bridged to a host byte stream. A background thread keeps
+/// reading from the stream, so polling it never blocks the emulation.
+pub struct Connection {
+    input: Receiver<u8>,
+    output: Box<dyn Write + Send>,
+    // terminals use LF where the firmware expects CR
+    translate_newlines: bool,
+    last_sent: u8,
+    // gives the terminal its settings back when the connection goes away
+    _raw_mode: Option<RawMode>,
+}
+
+impl Connection {
+    pub fn new<R, W>(mut reader: R, writer: W) -> Connection
+    where
+        R: Read + Send + 'static,
+        W: Write + Send + 'static,
+    {
+        let (sender, input) = mpsc::channel();
+
+        thread::spawn(move || {
+            let mut buffer = [0; 256];
+            while let Ok(count) = reader.read(&mut buffer) {
+                let bytes = &buffer[..count];
+                if count == 0 || bytes.iter().any(|&byte| sender.send(byte).is_err()) {
+                    break;
+                }
+            }
+        });
+
+        Connection {
+            input,
+            output: Box::new(writer),
+            translate_newlines: false,
+            last_sent: 0,
+            _raw_mode: None,
+        }
+    }
+
+    /// Bridges to stdin and stdout. Typed LFs are sent as CR, and a CR from
+    /// the firmware starts a new line, whether it is followed by LF or not.
+    ///
+    /// When stdin is a terminal it is switched to raw mode, so keys reach the
+    /// firmware as they are typed and only the firmware echoes them. Its
+    /// settings come back when the connection is dropped or Ctrl-C
+    /// interrupts the emulator.
+    pub fn terminal() -> Connection {
+        let raw_mode = RawMode::enable();
+        let mut connection = Connection::new(io::stdin(), io::stdout());
+        connection.translate_newlines = true;
+        connection._raw_mode = raw_mode;
+        connection
+    }
+
+    /// Opens a pseudo-terminal and returns it along with the path of its
+    /// slave side, for a terminal program like `screen` or `minicom`.
+    #[cfg(unix)]
+    pub fn pty() -> io::Result<(Connection, String)> {
+        use std::ffi::CStr;
+        use std::fs::File;
+        use std::os::raw::{c_char, c_int};
+        use std::os::unix::io::FromRawFd;
+
+        extern "C" {
+            fn posix_openpt(flags: c_int) -> c_int;
+            fn grantpt(fd: c_int) -> c_int;
+            fn unlockpt(fd: c_int) -> c_int;
+            fn ptsname(fd: c_int) -> *mut c_char;
+        }
+        const O_RDWR: c_int = 2;
+
+        // the calls only touch the descriptor they are given, which is owned
+        // by `master` as soon as it has been opened
+        let (master, path) = unsafe {
+            let fd = posix_openpt(O_RDWR);
+            if fd < 0 {
+                return Err(io::Error::last_os_error());
+            }
+            let master = File::from_raw_fd(fd);
+            if grantpt(fd) != 0 || unlockpt(fd) != 0 {
+                return Err(io::Error::last_os_error());
+            }
+            let name = ptsname(fd);
+            if name.is_null() {
+                return Err(io::Error::last_os_error());
+            }
+            (master, CStr::from_ptr(name).to_string_lossy().into_owned())
+        };
+
+        let reader = master.try_clone()?;
+        Ok((Connection::new(reader, master), path))
+    }
+
+    /// Listens on `port` of localhost and waits for a client to connect,
+    /// with `telnet localhost <port>` for instance.
+    pub fn tcp(port: u16) -> io::Result<Connection> {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port))?;
+        let (stream, _) = listener.accept()?;
+        stream.set_nodelay(true)?;
+
+        let reader = stream.try_clone()?;
+        Ok(Connection::new(reader, stream))
+    }
+}
+
+impl Serial for Connection {
+    fn send(&mut self, byte: u8) {
+        let result = if !self.translate_newlines {
+            self.output.write_all(&[byte])
+        } else {
+            match byte {
+                b'\r' => self.output.write_all(b"\n"),
+                b'\n' if self.last_sent == b'\r' => Ok(()),
+                _ => self.output.write_all(&[byte]),
+            }
+        };
+        self.last_sent = byte;
+
+        // a closed terminal or socket is like an unplugged cable
+        if result.is_ok() {
+            let _ = self.output.flush();
+        }
+    }
+
+    fn receive(&mut self) -> Option<u8> {
+        match self.input.try_recv() {
+            Ok(b'\n') if self.translate_newlines => Some(b'\r'),
+            Ok(byte) => Some(byte),
+            Err(TryRecvError::Empty) | Err(TryRecvError::Disconnected) => None,
+        }
+    }
+}
+
+/// The terminal on stdin in raw mode, restored on drop.
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+struct RawMode;
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+impl RawMode {
+    /// Switches off line editing and echo, or returns `None` when stdin is
+    /// not a terminal.
+    fn enable() -> Option<RawMode> {
+        let saved = termios::get()?;
+        let mut raw = saved;
+        raw.lflag &= !(termios::ICANON | termios::ECHO);
+        raw.cc[termios::VMIN] = 1;
+        raw.cc[termios::VTIME] = 0;
+
+        termios::save(saved);
+        termios::set(&raw);
+        Some(RawMode)
+    }
+}
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        termios::restore();
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+struct RawMode;
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+impl RawMode {
+    fn enable() -> Option<RawMode> {
+        None
+    }
+}
+
+/// Just enough of termios for `RawMode`, whose layout and constants differ
+/// between Linux and macOS.
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+mod termios {
+    use std::os::raw::{c_int, c_uchar};
+    use std::sync::OnceLock;
+
+    #[cfg(target_os = "linux")]
+    type Flags = std::os::raw::c_uint;
+    #[cfg(target_os = "macos")]
+    type Flags = std::os::raw::c_ulong;
+
+    #[cfg(target_os = "linux")]
+    mod consts {
+        pub const ICANON: super::Flags = 0o2;
+        pub const ECHO: super::Flags = 0o10;
+        pub const VTIME: usize = 5;
+        pub const VMIN: usize = 6;
+        pub const NCCS: usize = 32;
+    }
+    #[cfg(target_os = "macos")]
+    mod consts {
+        pub const ICANON: super::Flags = 0x100;
+        pub const ECHO: super::Flags = 0x8;
+        pub const VMIN: usize = 16;
+        pub const VTIME: usize = 17;
+        pub const NCCS: usize = 20;
+    }
+    pub use consts::*;
+
+    #[repr(C)]
+    #[derive(Clone, Copy)]
+    pub struct Termios {
+        iflag: Flags,
+        oflag: Flags,
+        cflag: Flags,
+        pub lflag: Flags,
+        #[cfg(target_os = "linux")]
+        line: c_uchar,
+        pub cc: [c_uchar; NCCS],
+        ispeed: Flags,
+        ospeed: Flags,
+    }
+
+    extern "C" {
+        fn tcgetattr(fd: c_int, termios: *mut Termios) -> c_int;
+        fn tcsetattr(fd: c_int, actions: c_int, termios: *const Termios) -> c_int;
+        fn signal(signal: c_int, handler: extern "C" fn(c_int)) -> usize;
+        fn _exit(status: c_int) -> !;
+    }
+    const STDIN: c_int = 0;
+    const TCSANOW: c_int = 0;
+    const SIGINT: c_int = 2;
+    const SIGTERM: c_int = 15;
+
+    // the settings to go back to, also needed by the signal handler
+    static SAVED: OnceLock<Termios> = OnceLock::new();
+
+    pub fn get() -> Option<Termios> {
+        let mut termios = std::mem::MaybeUninit::uninit();
+        // tcgetattr fills in the whole struct when it succeeds
+        unsafe {
+            if tcgetattr(STDIN, termios.as_mut_ptr()) != 0 {
+                return None;
+            }
+            Some(termios.assume_init())
+        }
+    }
+
+    pub fn set(termios: &Termios) {
+        unsafe {
+            tcsetattr(STDIN, TCSANOW, termios);
+        }
+    }
+
+    /// Keeps `termios` for `restore` and has Ctrl-C and SIGTERM restore it
+    /// before exiting, as the default action would leave the terminal raw.
+    pub fn save(termios: Termios) {
+        if SAVED.set(termios).is_ok() {
+            // the handler only calls async-signal-safe functions
+            unsafe {
+                signal(SIGINT, interrupted);
+                signal(SIGTERM, interrupted);
+            }
+        }
+    }
+
+    pub fn restore() {
+        if let Some(termios) = SAVED.get() {
+            set(termios);
+        }
+    }
+
+    extern "C" fn interrupted(signal: c_int) {
+        restore();
+        unsafe { _exit(128 + signal) }
+    }
+}
@@ -0,0 +1,668 @@
+//! The W65C22 Versatile Interface Adapter: two 8-bit ports with handshake
+//! lines, two 16-bit timers and a shift register
+
+use crate::device::{Context, Device};
+
+// register offsets, the VIA decodes the low four address lines
+pub const ORB: u16 = 0x0;
+pub const ORA: u16 = 0x1;
+pub const DDRB: u16 = 0x2;
+pub const DDRA: u16 = 0x3;
+pub const T1C_L: u16 = 0x4;
+pub const T1C_H: u16 = 0x5;
+pub const T1L_L: u16 = 0x6;
+pub const T1L_H: u16 = 0x7;
+pub const T2C_L: u16 = 0x8;
+pub const T2C_H: u16 = 0x9;
+pub const SR: u16 = 0xA;
+pub const ACR: u16 = 0xB;
+pub const PCR: u16 = 0xC;
+pub const IFR: u16 = 0xD;
+pub const IER: u16 = 0xE;
+/// ORA without the CA2 handshake.
+pub const ORA_NH: u16 = 0xF;
+
+/// Bits of IFR and IER.
+#[derive(Copy, Debug, Clone)]
+pub enum Interrupt {
+    Ca2 = 1 << 0,
+    Ca1 = 1 << 1,
+    ShiftRegister = 1 << 2,
+    Cb2 = 1 << 3,
+    Cb1 = 1 << 4,
+    Timer2 = 1 << 5,
+    Timer1 = 1 << 6,
+}
+
+/// Something wired to the eight pins of port A or B.
+pub trait Port {
+    /// Called whenever the levels driven by the VIA may have changed. Only
+    /// the bits set in `ddr` are outputs, the others float. `cycles` is the
+    /// time of the change, in CPU cycles.
+    fn write(&mut self, pins: u8, ddr: u8, cycles: u64);
+
+    /// Levels on the pins that are configured as inputs. Peeking at the port
+    /// register samples them as well, so reading must not change anything.
+    fn read(&self, cycles: u64) -> u8 {
+        0xFF
+    }
+}
+
+/// How CA2 or CB2 behaves, as set by the three PCR bits of the line.
+#[derive(Copy, Debug, Clone, PartialEq, Eq)]
+enum Control {
+    /// Input setting the IFR flag on an edge. Accessing the port register
+    /// clears the flag, unless the line is independent.
+    Input {
+        positive: bool,
+        independent: bool,
+    },
+    /// Output going low on a port access and back high on the active edge
+    /// of CA1 or CB1.
+    Handshake,
+    /// Output going low for a single cycle after a port access.
+    Pulse,
+    Manual(bool),
+}
+
+impl Control {
+    fn from_bits(bits: u8) -> Control {
+        match bits & 0x07 {
+            0 => Control::Input {
+                positive: false,
+                independent: false,
+            },
+            1 => Control::Input {
+                positive: false,
+                independent: true,
+            },
+            2 => Control::Input {
+                positive: true,
+                independent: false,
+            },
+            3 => Control::Input {
+                positive: true,
+                independent: true,
+            },
+            4 => Control::Handshake,
+            5 => Control::Pulse,
+            6 => Control::Manual(false),
+            _ => Control::Manual(true),
+        }
+    }
+}
+
+#[derive(Default)]
+struct PortState {
+    output: u8,
+    ddr: u8,
+    // levels driven from outside when no `Port` is connected
+    input: u8,
+    latch: u8,
+    // control lines: C*1 is always an input, C*2 either way
+    c1: bool,
+    c2_input: bool,
+    c2_output: bool,
+    // cycles left until a pulse on C*2 ends
+    pulse: u8,
+    port: Option<Box<dyn Port>>,
+}
+
+impl PortState {
+    fn input(&self, cycles: u64) -> u8 {
+        match self.port.as_ref() {
+            Some(port) => port.read(cycles),
+            None => self.input,
+        }
+    }
+
+    fn pins(&self, cycles: u64) -> u8 {
+        (self.output & self.ddr) | (self.input(cycles) & !self.ddr)
+    }
+
+    fn update(&mut self, pins: u8, cycles: u64) {
+        let ddr = self.ddr;
+        if let Some(port) = self.port.as_mut() {
+            port.write(pins, ddr, cycles);
+        }
+    }
+}
+
+pub struct Via {
+    a: PortState,
+    b: PortState,
+
+    t1_counter: u16,
+    t1_latch: u16,
+    // whether T1 still fires in one-shot mode
+    t1_armed: bool,
+    // set when the counter needs reloading from the latch on the next cycle
+    t1_reload: bool,
+    pb7: bool,
+
+    t2_counter: u16,
+    t2_latch_low: u8,
+    t2_armed: bool,
+
+    sr: u8,
+    sr_count: u8,
+    sr_active: bool,
+    // cycles until the internal shift clock toggles next
+    sr_clock: u16,
+    cb1_output: bool,
+
+    acr: u8,
+    pcr: u8,
+    ifr: u8,
+    ier: u8,
+
+    cycles: u64,
+}
+
+impl Default for Via {
+    fn default() -> Via {
+        Via::new()
+    }
+}
+
+impl Via {
+    pub fn new() -> Via {
+        Via {
+            a: PortState {
+                input: 0xFF,
+                c1: true,
+                c2_input: true,
+                c2_output: true,
+                ..PortState::default()
+            },
+            b: PortState {
+                input: 0xFF,
+                c1: true,
+                c2_input: true,
+                c2_output: true,
+                ..PortState::default()
+            },
+
+            t1_counter: 0xFFFF,
+            t1_latch: 0xFFFF,
+            t1_armed: false,
+            t1_reload: false,
+            pb7: true,
+
+            t2_counter: 0xFFFF,
+            t2_latch_low: 0xFF,
+            t2_armed: false,
+
+            sr: 0,
+            sr_count: 0,
+            sr_active: false,
+            sr_clock: 0,
+            cb1_output: true,
+
+            acr: 0,
+            pcr: 0,
+            ifr: 0,
+            ier: 0,
+
+            cycles: 0,
+        }
+    }
+
+    /// Wires `port` to the pins of port A.
+    pub fn connect_port_a<P: Port + 'static>(&mut self, port: P) {
+        self.a.port = Some(Box::new(port));
+        let pins = self.a.pins(self.cycles);
+        self.a.update(pins, self.cycles);
+    }
+
+    pub fn connect_port_b<P: Port + 'static>(&mut self, port: P) {
+        self.b.port = Some(Box::new(port));
+        let pins = self.port_b();
+        self.b.update(pins, self.cycles);
+    }
+
+    /// Levels on the pins of port A.
+    pub fn port_a(&self) -> u8 {
+        self.a.pins(self.cycles)
+    }
+
+    /// Levels on the pins of port B, with PB7 driven by T1 if enabled.
+    pub fn port_b(&self) -> u8 {
+        let pins = self.b.pins(self.cycles);
+        if self.pb7_enabled() {
+            (pins & 0x7F) | (self.pb7 as u8) << 7
+        } else {
+            pins
+        }
+    }
+
+    /// Drives the input pins of port A, when no `Port` is connected.
+    pub fn set_port_a_input(&mut self, value: u8) {
+        self.a.input = value;
+    }
+
+    /// Drives the input pins of port B. In pulse counting mode, falling edges
+    /// on PB6 count T2 down.
+    pub fn set_port_b_input(&mut self, value: u8) {
+        let falling = self.b.input & 0x40 != 0 && value & 0x40 == 0;
+        self.b.input = value;
+
+        if falling && self.t2_counts_pulses() {
+            self.count_t2();
+        }
+    }
+
+    pub fn set_ca1(&mut self, level: bool) {
+        let positive = self.pcr & 0x01 != 0;
+        if self.a.c1 != level && level == positive {
+            self.ifr |= Interrupt::Ca1 as u8;
+            if self.acr & 0x01 != 0 {
+                self.a.latch = self.a.input(self.cycles);
+            }
+            if self.ca2_control() == Control::Handshake {
+                self.a.c2_output = true;
+            }
+        }
+        self.a.c1 = level;
+    }
+
+    pub fn set_ca2(&mut self, level: bool) {
+        if let Control::Input { positive, .. } = self.ca2_control() {
+            if self.a.c2_input != level && level == positive {
+                self.ifr |= Interrupt::Ca2 as u8;
+            }
+        }
+        self.a.c2_input = level;
+    }
+
+    /// Drives CB1, which also clocks the shift register in the external
+    /// clock modes.
+    pub fn set_cb1(&mut self, level: bool) {
+        let positive = self.pcr & 0x10 != 0;
+        if self.b.c1 != level && level == positive {
+            self.ifr |= Interrupt::Cb1 as u8;
+            if self.acr & 0x02 != 0 {
+                self.b.latch = self.b.input(self.cycles);
+            }
+            if self.cb2_control() == Control::Handshake {
+                self.b.c2_output = true;
+            }
+        }
+        if self.b.c1 != level && self.sr_mode() & 0x03 == 0x03 {
+            // external clock: shifting in on the rising, out on the falling
+            // edge
+            let shift_in = self.sr_mode() & 0x04 == 0;
+            if level == shift_in {
+                self.shift();
+            }
+        }
+        self.b.c1 = level;
+    }
+
+    pub fn set_cb2(&mut self, level: bool) {
+        if let Control::Input { positive, .. } = self.cb2_control() {
+            if self.b.c2_input != level && level == positive {
+                self.ifr |= Interrupt::Cb2 as u8;
+            }
+        }
+        self.b.c2_input = level;
+    }
+
+    /// Level of CA2 when it is an output.
+    pub fn ca2(&self) -> bool {
+        match self.ca2_control() {
+            Control::Manual(level) => level,
+            Control::Handshake | Control::Pulse => self.a.c2_output,
+            Control::Input { .. } => self.a.c2_input,
+        }
+    }
+
+    /// Level of CB2, which is also the data line of the shift register.
+    pub fn cb2(&self) -> bool {
+        if self.sr_mode() & 0x04 != 0 {
+            return self.b.c2_output;
+        }
+        match self.cb2_control() {
+            Control::Manual(level) => level,
+            Control::Handshake | Control::Pulse => self.b.c2_output,
+            Control::Input { .. } => self.b.c2_input,
+        }
+    }
+
+    /// Level of CB1, which the shift register drives as its clock output in
+    /// the internally clocked modes.
+    pub fn cb1(&self) -> bool {
+        match self.sr_mode() {
+            0x00 | 0x03 | 0x07 => self.b.c1,
+            _ => self.cb1_output,
+        }
+    }
+
+    /// Whether the VIA pulls its IRQ output low.
+    pub fn irq(&self) -> bool {
+        self.ifr & self.ier & 0x7F != 0
+    }
+
+    fn ca2_control(&self) -> Control {
+        Control::from_bits(self.pcr >> 1)
+    }
+
+    fn cb2_control(&self) -> Control {
+        Control::from_bits(self.pcr >> 5)
+    }
+
+    fn t1_free_running(&self) -> bool {
+        self.acr & 0x40 != 0
+    }
+
+    fn pb7_enabled(&self) -> bool {
+        self.acr & 0x80 != 0
+    }
+
+    fn t2_counts_pulses(&self) -> bool {
+        self.acr & 0x20 != 0
+    }
+
+    fn sr_mode(&self) -> u8 {
+        (self.acr >> 2) & 0x07
+    }
+
+    fn ifr(&self) -> u8 {
+        let irq = if self.irq() { 0x80 } else { 0x00 };
+        self.ifr | irq
+    }
+
+    /// Side effects of accessing ORA, or ORB for `port_b`: the C*1 and C*2
+    /// flags are cleared and the C*2 handshake starts.
+    fn access_port(&mut self, port_b: bool, write: bool) {
+        let (c1, c2, control) = if port_b {
+            (Interrupt::Cb1, Interrupt::Cb2, self.cb2_control())
+        } else {
+            (Interrupt::Ca1, Interrupt::Ca2, self.ca2_control())
+        };
+
+        self.ifr &= !(c1 as u8);
+        if let Control::Input {
+            independent: false, ..
+        } = control
+        {
+            self.ifr &= !(c2 as u8);
+        }
+
+        // CB2 only hands shakes on writes, as port B is meant for output
+        if port_b && !write {
+            return;
+        }
+        let state = if port_b { &mut self.b } else { &mut self.a };
+        match control {
+            Control::Handshake => state.c2_output = false,
+            Control::Pulse => {
+                state.c2_output = false;
+                state.pulse = 1;
+            }
+            _ => {}
+        }
+    }
+
+    /// Value of ORA as read by the CPU, without the side effects of the
+    /// access.
+    fn port_a_register(&self) -> u8 {
+        if self.acr & 0x01 != 0 {
+            self.a.latch
+        } else {
+            self.a.pins(self.cycles)
+        }
+    }
+
+    fn port_b_register(&self) -> u8 {
+        // output pins always read back the output register
+        let input = if self.acr & 0x02 != 0 {
+            self.b.latch
+        } else {
+            self.b.input(self.cycles)
+        };
+        let value = (self.b.output & self.b.ddr) | (input & !self.b.ddr);
+        if self.pb7_enabled() {
+            (value & 0x7F) | (self.pb7 as u8) << 7
+        } else {
+            value
+        }
+    }
+
+    fn start_shift_register(&mut self) {
+        self.ifr &= !(Interrupt::ShiftRegister as u8);
+        self.sr_count = 0;
+        self.sr_active = self.sr_mode() != 0;
+    }
+
+    /// Shifts a single bit. Shifting out rotates the register, so the data
+    /// stays in it and a free-running shift repeats it forever.
+    fn shift(&mut self) {
+        if !self.sr_active {
+            return;
+        }
+        let mode = self.sr_mode();
+
+        if mode & 0x04 != 0 {
+            self.b.c2_output = self.sr & 0x80 != 0;
+            self.sr = self.sr.rotate_left(1);
+        } else {
+            self.sr = self.sr << 1 | self.b.c2_input as u8;
+        }
+
+        // mode 100 runs forever without interrupts
+        if mode != 0x04 {
+            self.sr_count += 1;
+            if self.sr_count == 8 {
+                self.sr_active = false;
+                self.ifr |= Interrupt::ShiftRegister as u8;
+            }
+        }
+    }
+
+    fn count_t2(&mut self) {
+        let (counter, underflow) = self.t2_counter.overflowing_sub(1);
+        self.t2_counter = counter;
+
+        if underflow && self.t2_armed {
+            self.t2_armed = false;
+            self.ifr |= Interrupt::Timer2 as u8;
+        }
+    }
+
+    /// Advances the timers, the shift register and the C*2 pulses by a
+    /// single cycle.
+    fn clock(&mut self) {
+        if self.t1_reload {
+            self.t1_reload = false;
+            self.t1_counter = self.t1_latch;
+        } else {
+            let (counter, underflow) = self.t1_counter.overflowing_sub(1);
+            self.t1_counter = counter;
+
+            if underflow {
+                if self.t1_armed {
+                    self.ifr |= Interrupt::Timer1 as u8;
+                    // one-shot mode ends with PB7 high, free-running mode
+                    // turns it into a square wave
+                    self.pb7 = !self.t1_free_running() || !self.pb7;
+                    self.t1_armed = self.t1_free_running();
+                }
+                if self.t1_free_running() {
+                    self.t1_reload = true;
+                }
+            }
+        }
+
+        if !self.t2_counts_pulses() {
+            self.count_t2();
+        }
+
+        // the internally clocked shift modes toggle CB1 as their clock,
+        // every cycle under phi2 or whenever the low byte of T2 times out
+        let mode = self.sr_mode();
+        if self.sr_active && mode & 0x03 != 0x03 && mode != 0 {
+            if self.sr_clock == 0 {
+                self.sr_clock = if mode & 0x03 == 0x02 {
+                    1
+                } else {
+                    self.t2_latch_low as u16 + 2
+                };
+            }
+            self.sr_clock -= 1;
+            if self.sr_clock == 0 {
+                self.cb1_output = !self.cb1_output;
+                if self.cb1_output {
+                    self.shift();
+                }
+            }
+        }
+
+        for state in [&mut self.a, &mut self.b].iter_mut() {
+            if state.pulse > 0 {
+                state.pulse -= 1;
+                if state.pulse == 0 {
+                    state.c2_output = true;
+                }
+            }
+        }
+    }
+}
+
+impl Device for Via {
+    fn read(&mut self, offset: u16, context: &mut Context) -> u8 {
+        let value = match offset & 0x0F {
+            ORB => {
+                self.access_port(true, false);
+                self.port_b_register()
+            }
+            ORA => {
+                self.access_port(false, false);
+                self.port_a_register()
+            }
+            DDRB => self.b.ddr,
+            DDRA => self.a.ddr,
+            T1C_L => {
+                self.ifr &= !(Interrupt::Timer1 as u8);
+                self.t1_counter as u8
+            }
+            T1C_H => (self.t1_counter >> 8) as u8,
+            T1L_L => self.t1_latch as u8,
+            T1L_H => (self.t1_latch >> 8) as u8,
+            T2C_L => {
+                self.ifr &= !(Interrupt::Timer2 as u8);
+                self.t2_counter as u8
+            }
+            T2C_H => (self.t2_counter >> 8) as u8,
+            SR => {
+                self.start_shift_register();
+                self.sr
+            }
+            ACR => self.acr,
+            PCR => self.pcr,
+            IFR => self.ifr(),
+            IER => self.ier | 0x80,
+            _ => self.port_a_register(),
+        };
+
+        context.set_irq(self.irq());
+        value
+    }
+
+    fn write(&mut self, offset: u16, value: u8, context: &mut Context) {
+        match offset & 0x0F {
+            ORB => {
+                self.access_port(true, true);
+                self.b.output = value;
+            }
+            ORA => {
+                self.access_port(false, true);
+                self.a.output = value;
+            }
+            DDRB => self.b.ddr = value,
+            DDRA => self.a.ddr = value,
+            T1C_L | T1L_L => self.t1_latch = (self.t1_latch & 0xFF00) | value as u16,
+            T1C_H => {
+                self.t1_latch = (self.t1_latch & 0x00FF) | (value as u16) << 8;
+                self.t1_counter = self.t1_latch;
+                self.t1_armed = true;
+                self.t1_reload = false;
+                self.ifr &= !(Interrupt::Timer1 as u8);
+                self.pb7 = false;
+            }
+            T1L_H => {
+                self.t1_latch = (self.t1_latch & 0x00FF) | (value as u16) << 8;
+                self.ifr &= !(Interrupt::Timer1 as u8);
+            }
+            T2C_L => self.t2_latch_low = value,
+            T2C_H => {
+                self.t2_counter = (value as u16) << 8 | self.t2_latch_low as u16;
+                self.t2_armed = true;
+                self.ifr &= !(Interrupt::Timer2 as u8);
+            }
+            SR => {
+                self.sr = value;
+                self.start_shift_register();
+            }
+            ACR => self.acr = value,
+            PCR => {
+                self.pcr = value;
+                if let Control::Manual(level) = self.ca2_control() {
+                    self.a.c2_output = level;
+                }
+                if let Control::Manual(level) = self.cb2_control() {
+                    self.b.c2_output = level;
+                }
+            }
+            IFR => self.ifr &= !value,
+            IER => {
+                if value & 0x80 != 0 {
+                    self.ier |= value & 0x7F;
+                } else {
+                    self.ier &= !value;
+                }
+            }
+            _ => self.a.output = value,
+        }
+
+        let pins = self.a.pins(self.cycles);
+        self.a.update(pins, self.cycles);
+        let pins = self.port_b();
+        self.b.update(pins, self.cycles);
+        context.set_irq(self.irq());
+    }
+
+    fn peek(&self, offset: u16) -> u8 {
+        match offset & 0x0F {
+            ORB => self.port_b_register(),
+            DDRB => self.b.ddr,
+            DDRA => self.a.ddr,
+            T1C_L => self.t1_counter as u8,
+            T1C_H => (self.t1_counter >> 8) as u8,
+            T1L_L => self.t1_latch as u8,
+            T1L_H => (self.t1_latch >> 8) as u8,
+            T2C_L => self.t2_counter as u8,
+            T2C_H => (self.t2_counter >> 8) as u8,
+            SR => self.sr,
+            ACR => self.acr,
+            PCR => self.pcr,
+            IFR => self.ifr(),
+            IER => self.ier | 0x80,
+            _ => self.port_a_register(),
+        }
+    }
+
+    fn tick(&mut self, context: &mut Context) {
+        let pb7 = self.pb7;
+        while self.cycles < context.cycles() {
+            self.clock();
+            self.cycles += 1;
+        }
+
+        if pb7 != self.pb7 && self.pb7_enabled() {
+            let pins = self.port_b();
+            self.b.update(pins, self.cycles);
+        }
+        context.set_irq(self.irq());
+    }
+}
@@ -0,0 +1,89 @@
+//! Errors reported when loading images and running the CPU
+
+use std::fmt;
+use std::io;
+
+#[derive(Debug)]
+pub enum VolveError {
+    Io(io::Error),
+    /// An image of `size` bytes does not fit into the `capacity` bytes it
+    /// is loaded into.
+    ImageTooLarge {
+        size: usize,
+        capacity: usize,
+    },
+    /// The CPU hit an opcode its variant does not decode, at a 16-bit
+    /// address or a 24-bit one on the 65C816.
+    InvalidOpcode {
+        opcode: u8,
+        address: u32,
+    },
+    /// The CPU has been stopped by STP, or a JAM on NMOS parts, and only a
+    /// reset gets it going again.
+    Halted,
+    /// A malformed record in an Intel HEX or S-record file, `line` counting
+    /// from 1.
+    BadRecord {
+        line: usize,
+        reason: &'static str,
+    },
+    /// An access the bus refused, like a write to ROM under
+    /// `RomWritePolicy::Fault`.
+    BusFault {
+        address: u16,
+    },
+    /// A region of a `MemoryMap` that cannot be mapped, from `start` to
+    /// `end` inclusive.
+    InvalidRegion {
+        start: u16,
+        end: u16,
+        reason: &'static str,
+    },
+    /// An image covers an address with nothing to load it into, like a
+    /// device or an unmapped hole.
+    NotLoadable {
+        address: u16,
+    },
+}
+
+pub type Result<T> = std::result::Result<T, VolveError>;
+
+impl fmt::Display for VolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VolveError::Io(error) => write!(f, "{}", error),
+            VolveError::ImageTooLarge { size, capacity } => write!(
+                f,
+                "image of {} bytes does not fit into {} bytes",
+                size, capacity
+            ),
+            VolveError::InvalidOpcode { opcode, address } => {
+                write!(f, "invalid opcode ${:02X} at ${:04X}", opcode, address)
+            }
+            VolveError::BadRecord { line, reason } => write!(f, "line {}: {}", line, reason),
+            VolveError::Halted => write!(f, "the CPU has been halted"),
+            VolveError::BusFault { address } => write!(f, "bus fault at ${:04X}", address),
+            VolveError::InvalidRegion { start, end, reason } => {
+                write!(f, "region ${:04X}-${:04X}: {}", start, end, reason)
+            }
+            VolveError::NotLoadable { address } => {
+                write!(f, "nothing to load into at ${:04X}", address)
+            }
+        }
+    }
+}
+
+impl std::error::Error for VolveError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            VolveError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for VolveError {
+    fn from(error: io::Error) -> VolveError {
+        VolveError::Io(error)
+    }
+}
@@ -7,6 +7,7 @@ pub enum AddressingMode {
     ZeroPageX,
     ZeroPageY,
     Relative,
+    ZeroPageRelative,
     Stack,
     Absolute,
     AbsoluteX,
@@ -21,7 +22,11 @@ pub enum AddressingMode {
 #[derive(Copy, Debug, Clone)]
 pub enum OpCode {
     ADC,
+    ALR,
+    ANC,
     AND,
+    ANE,
+    ARR,
     ASL,
     BBR0,
     BBR1,
@@ -46,6 +51,7 @@ pub enum OpCode {
     BMI,
     BNE,
     BPL,
+    BRA,
     BRK,
     BVC,
     BVS,
@@ -65,19 +71,26 @@ pub enum OpCode {
     INX,
     INY,
     ISB,
+    JAM,
     JMP,
     JSR,
+    LAS,
     LAX,
     LDA,
     LDX,
     LDY,
     LSR,
+    LXA,
     NOP,
     ORA,
     PHA,
     PHP,
+    PHX,
+    PHY,
     PLA,
     PLP,
+    PLX,
+    PLY,
     RLA,
     RMB0,
     RMB1,
@@ -94,14 +107,29 @@ pub enum OpCode {
     RTS,
     SAX,
     SBC,
+    SBX,
     SEC,
     SED,
     SEI,
+    SHA,
+    SHX,
+    SHY,
     SLO,
+    SMB0,
+    SMB1,
+    SMB2,
+    SMB3,
+    SMB4,
+    SMB5,
+    SMB6,
+    SMB7,
     SRE,
     STA,
+    STP,
     STX,
     STY,
+    STZ,
+    TAS,
     TAX,
     TAY,
     TRB,
@@ -110,785 +138,3215 @@ pub enum OpCode {
     TXA,
     TXS,
     TYA,
+    WAI,
+}
+
+impl OpCode {
+    /// RMB, SMB, BBR and BBS, which Rockwell added to the 65C02.
+    pub fn is_bit_instruction(self) -> bool {
+        matches!(
+            self,
+            OpCode::BBR0
+                | OpCode::BBR1
+                | OpCode::BBR2
+                | OpCode::BBR3
+                | OpCode::BBR4
+                | OpCode::BBR5
+                | OpCode::BBR6
+                | OpCode::BBR7
+                | OpCode::BBS0
+                | OpCode::BBS1
+                | OpCode::BBS2
+                | OpCode::BBS3
+                | OpCode::BBS4
+                | OpCode::BBS5
+                | OpCode::BBS6
+                | OpCode::BBS7
+                | OpCode::RMB0
+                | OpCode::RMB1
+                | OpCode::RMB2
+                | OpCode::RMB3
+                | OpCode::RMB4
+                | OpCode::RMB5
+                | OpCode::RMB6
+                | OpCode::RMB7
+                | OpCode::SMB0
+                | OpCode::SMB1
+                | OpCode::SMB2
+                | OpCode::SMB3
+                | OpCode::SMB4
+                | OpCode::SMB5
+                | OpCode::SMB6
+                | OpCode::SMB7
+        )
+    }
+
+    /// WAI and STP, which only exist on the WDC parts.
+    pub fn is_wdc_extension(self) -> bool {
+        matches!(self, OpCode::WAI | OpCode::STP)
+    }
+
+    /// Whether the instruction loads the program counter itself instead of
+    /// falling through to the next instruction.
+    pub fn transfers_control(self) -> bool {
+        matches!(
+            self,
+            OpCode::BBR0
+                | OpCode::BBR1
+                | OpCode::BBR2
+                | OpCode::BBR3
+                | OpCode::BBR4
+                | OpCode::BBR5
+                | OpCode::BBR6
+                | OpCode::BBR7
+                | OpCode::BBS0
+                | OpCode::BBS1
+                | OpCode::BBS2
+                | OpCode::BBS3
+                | OpCode::BBS4
+                | OpCode::BBS5
+                | OpCode::BBS6
+                | OpCode::BBS7
+                | OpCode::BCC
+                | OpCode::BCS
+                | OpCode::BEQ
+                | OpCode::BMI
+                | OpCode::BNE
+                | OpCode::BPL
+                | OpCode::BRA
+                | OpCode::BRK
+                | OpCode::BVC
+                | OpCode::BVS
+                | OpCode::JMP
+                | OpCode::JSR
+                | OpCode::RTI
+                | OpCode::RTS
+        )
+    }
 }
 
 #[derive(Copy, Debug, Clone)]
 pub struct Instruction {
     pub opcode: OpCode,
     pub mode: AddressingMode,
+    /// Encoded size in bytes, including the opcode itself.
+    pub length: u8,
+    /// Base cycle count, without any penalty cycles.
+    pub cycles: u8,
 }
 
+/// Decode table of the W65C02S, indexed by opcode byte.
+///
+/// Lengths and base cycle counts follow the WDC datasheet. Penalty cycles
+/// (page crossings, taken branches, decimal mode) are not included.
 pub static OP_CODES: [Option<Instruction>; 256] = [
+    // 0x00
     Some(Instruction {
         opcode: OpCode::BRK,
-        mode: AddressingMode::Implied,
+        mode: AddressingMode::Stack,
+        length: 2,
+        cycles: 7,
     }),
     Some(Instruction {
         opcode: OpCode::ORA,
         mode: AddressingMode::IndexedIndirectX,
+        length: 2,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::NOP,
+        mode: AddressingMode::Immediate,
+        length: 2,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::NOP,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 1,
     }),
-    None,
-    None,
     Some(Instruction {
         opcode: OpCode::TSB,
         mode: AddressingMode::ZeroPage,
+        length: 2,
+        cycles: 5,
     }),
     Some(Instruction {
         opcode: OpCode::ORA,
         mode: AddressingMode::ZeroPage,
+        length: 2,
+        cycles: 3,
     }),
     Some(Instruction {
         opcode: OpCode::ASL,
         mode: AddressingMode::ZeroPage,
+        length: 2,
+        cycles: 5,
     }),
-    // 7
     Some(Instruction {
         opcode: OpCode::RMB0,
         mode: AddressingMode::ZeroPage,
+        length: 2,
+        cycles: 5,
     }),
     Some(Instruction {
         opcode: OpCode::PHP,
-        mode: AddressingMode::Implied,
+        mode: AddressingMode::Stack,
+        length: 1,
+        cycles: 3,
     }),
     Some(Instruction {
         opcode: OpCode::ORA,
         mode: AddressingMode::Immediate,
+        length: 2,
+        cycles: 2,
     }),
     Some(Instruction {
         opcode: OpCode::ASL,
         mode: AddressingMode::Accumulator,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::NOP,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 1,
     }),
-    // 0b
-    None,
     Some(Instruction {
         opcode: OpCode::TSB,
         mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 6,
     }),
     Some(Instruction {
         opcode: OpCode::ORA,
         mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 4,
     }),
     Some(Instruction {
         opcode: OpCode::ASL,
         mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 6,
     }),
     Some(Instruction {
         opcode: OpCode::BBR0,
-        mode: AddressingMode::Relative,
+        mode: AddressingMode::ZeroPageRelative,
+        length: 3,
+        cycles: 5,
     }),
+    // 0x10
     Some(Instruction {
         opcode: OpCode::BPL,
         mode: AddressingMode::Relative,
+        length: 2,
+        cycles: 2,
     }),
-    // 11
     Some(Instruction {
         opcode: OpCode::ORA,
         mode: AddressingMode::IndirectIndexedY,
+        length: 2,
+        cycles: 5,
     }),
     Some(Instruction {
         opcode: OpCode::ORA,
         mode: AddressingMode::Indirect,
+        length: 2,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::NOP,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 1,
     }),
-    None,
     Some(Instruction {
         opcode: OpCode::TRB,
         mode: AddressingMode::ZeroPage,
+        length: 2,
+        cycles: 5,
     }),
     Some(Instruction {
         opcode: OpCode::ORA,
         mode: AddressingMode::ZeroPageX,
+        length: 2,
+        cycles: 4,
     }),
     Some(Instruction {
         opcode: OpCode::ASL,
         mode: AddressingMode::ZeroPageX,
+        length: 2,
+        cycles: 6,
     }),
-    // 17
     Some(Instruction {
         opcode: OpCode::RMB1,
         mode: AddressingMode::ZeroPage,
+        length: 2,
+        cycles: 5,
     }),
     Some(Instruction {
         opcode: OpCode::CLC,
         mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
     }),
     Some(Instruction {
         opcode: OpCode::ORA,
         mode: AddressingMode::AbsoluteY,
+        length: 3,
+        cycles: 4,
     }),
-    // 1a
     Some(Instruction {
         opcode: OpCode::INC,
         mode: AddressingMode::Accumulator,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::NOP,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 1,
     }),
-    // 1b
-    None,
-    // 1c
     Some(Instruction {
         opcode: OpCode::TRB,
         mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 6,
     }),
     Some(Instruction {
         opcode: OpCode::ORA,
         mode: AddressingMode::AbsoluteX,
+        length: 3,
+        cycles: 4,
     }),
     Some(Instruction {
         opcode: OpCode::ASL,
         mode: AddressingMode::AbsoluteX,
+        length: 3,
+        cycles: 6,
     }),
-    // 1f
     Some(Instruction {
         opcode: OpCode::BBR1,
-        mode: AddressingMode::Relative,
+        mode: AddressingMode::ZeroPageRelative,
+        length: 3,
+        cycles: 5,
     }),
-    // 20
+    // 0x20
     Some(Instruction {
         opcode: OpCode::JSR,
         mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 6,
     }),
     Some(Instruction {
         opcode: OpCode::AND,
         mode: AddressingMode::IndexedIndirectX,
+        length: 2,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::NOP,
+        mode: AddressingMode::Immediate,
+        length: 2,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::NOP,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 1,
     }),
-    None,
-    None,
     Some(Instruction {
         opcode: OpCode::BIT,
         mode: AddressingMode::ZeroPage,
+        length: 2,
+        cycles: 3,
     }),
     Some(Instruction {
         opcode: OpCode::AND,
         mode: AddressingMode::ZeroPage,
+        length: 2,
+        cycles: 3,
     }),
     Some(Instruction {
         opcode: OpCode::ROL,
         mode: AddressingMode::ZeroPage,
+        length: 2,
+        cycles: 5,
     }),
-    // 27
     Some(Instruction {
         opcode: OpCode::RMB2,
         mode: AddressingMode::ZeroPage,
+        length: 2,
+        cycles: 5,
     }),
     Some(Instruction {
         opcode: OpCode::PLP,
-        mode: AddressingMode::Implied,
+        mode: AddressingMode::Stack,
+        length: 1,
+        cycles: 4,
     }),
     Some(Instruction {
         opcode: OpCode::AND,
         mode: AddressingMode::Immediate,
+        length: 2,
+        cycles: 2,
     }),
-    // 2a
     Some(Instruction {
         opcode: OpCode::ROL,
         mode: AddressingMode::Accumulator,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::NOP,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 1,
     }),
-    // 2b
-    None,
     Some(Instruction {
         opcode: OpCode::BIT,
         mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 4,
     }),
     Some(Instruction {
         opcode: OpCode::AND,
         mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 4,
     }),
     Some(Instruction {
         opcode: OpCode::ROL,
         mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 6,
     }),
-    // 2f
     Some(Instruction {
         opcode: OpCode::BBR2,
-        mode: AddressingMode::Relative,
+        mode: AddressingMode::ZeroPageRelative,
+        length: 3,
+        cycles: 5,
     }),
+    // 0x30
     Some(Instruction {
         opcode: OpCode::BMI,
         mode: AddressingMode::Relative,
+        length: 2,
+        cycles: 2,
     }),
     Some(Instruction {
         opcode: OpCode::AND,
         mode: AddressingMode::IndirectIndexedY,
+        length: 2,
+        cycles: 5,
     }),
     Some(Instruction {
         opcode: OpCode::AND,
         mode: AddressingMode::Indirect,
+        length: 2,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::NOP,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 1,
     }),
-    // 33
-    None,
     Some(Instruction {
         opcode: OpCode::BIT,
         mode: AddressingMode::ZeroPageX,
+        length: 2,
+        cycles: 4,
     }),
     Some(Instruction {
         opcode: OpCode::AND,
         mode: AddressingMode::ZeroPageX,
+        length: 2,
+        cycles: 4,
     }),
     Some(Instruction {
         opcode: OpCode::ROL,
         mode: AddressingMode::ZeroPageX,
+        length: 2,
+        cycles: 6,
     }),
     Some(Instruction {
         opcode: OpCode::RMB3,
         mode: AddressingMode::ZeroPage,
+        length: 2,
+        cycles: 5,
     }),
     Some(Instruction {
         opcode: OpCode::SEC,
         mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
     }),
     Some(Instruction {
         opcode: OpCode::AND,
         mode: AddressingMode::AbsoluteY,
+        length: 3,
+        cycles: 4,
     }),
-    // 3a
     Some(Instruction {
         opcode: OpCode::DEC,
         mode: AddressingMode::Accumulator,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::NOP,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 1,
+    }),
+    Some(Instruction {
+        opcode: OpCode::BIT,
+        mode: AddressingMode::AbsoluteX,
+        length: 3,
+        cycles: 4,
     }),
-    None,
-    None,
     Some(Instruction {
         opcode: OpCode::AND,
         mode: AddressingMode::AbsoluteX,
+        length: 3,
+        cycles: 4,
     }),
     Some(Instruction {
         opcode: OpCode::ROL,
         mode: AddressingMode::AbsoluteX,
+        length: 3,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::BBR3,
+        mode: AddressingMode::ZeroPageRelative,
+        length: 3,
+        cycles: 5,
     }),
-    None,
+    // 0x40
     Some(Instruction {
         opcode: OpCode::RTI,
-        mode: AddressingMode::Implied,
+        mode: AddressingMode::Stack,
+        length: 1,
+        cycles: 6,
     }),
     Some(Instruction {
         opcode: OpCode::EOR,
         mode: AddressingMode::IndexedIndirectX,
+        length: 2,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::NOP,
+        mode: AddressingMode::Immediate,
+        length: 2,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::NOP,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 1,
+    }),
+    Some(Instruction {
+        opcode: OpCode::NOP,
+        mode: AddressingMode::ZeroPage,
+        length: 2,
+        cycles: 3,
     }),
-    None,
-    None,
-    None,
     Some(Instruction {
         opcode: OpCode::EOR,
         mode: AddressingMode::ZeroPage,
+        length: 2,
+        cycles: 3,
     }),
     Some(Instruction {
         opcode: OpCode::LSR,
         mode: AddressingMode::ZeroPage,
+        length: 2,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::RMB4,
+        mode: AddressingMode::ZeroPage,
+        length: 2,
+        cycles: 5,
     }),
-    None,
     Some(Instruction {
         opcode: OpCode::PHA,
-        mode: AddressingMode::Implied,
+        mode: AddressingMode::Stack,
+        length: 1,
+        cycles: 3,
     }),
     Some(Instruction {
         opcode: OpCode::EOR,
         mode: AddressingMode::Immediate,
+        length: 2,
+        cycles: 2,
     }),
     Some(Instruction {
         opcode: OpCode::LSR,
         mode: AddressingMode::Accumulator,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::NOP,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 1,
     }),
-    None,
     Some(Instruction {
         opcode: OpCode::JMP,
         mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 3,
     }),
     Some(Instruction {
         opcode: OpCode::EOR,
         mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 4,
     }),
     Some(Instruction {
         opcode: OpCode::LSR,
         mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::BBR4,
+        mode: AddressingMode::ZeroPageRelative,
+        length: 3,
+        cycles: 5,
     }),
-    None,
+    // 0x50
     Some(Instruction {
         opcode: OpCode::BVC,
         mode: AddressingMode::Relative,
+        length: 2,
+        cycles: 2,
     }),
     Some(Instruction {
         opcode: OpCode::EOR,
         mode: AddressingMode::IndirectIndexedY,
+        length: 2,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::EOR,
+        mode: AddressingMode::Indirect,
+        length: 2,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::NOP,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 1,
+    }),
+    Some(Instruction {
+        opcode: OpCode::NOP,
+        mode: AddressingMode::ZeroPageX,
+        length: 2,
+        cycles: 4,
     }),
-    None,
-    None,
-    None,
     Some(Instruction {
         opcode: OpCode::EOR,
         mode: AddressingMode::ZeroPageX,
+        length: 2,
+        cycles: 4,
     }),
     Some(Instruction {
         opcode: OpCode::LSR,
         mode: AddressingMode::ZeroPageX,
+        length: 2,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::RMB5,
+        mode: AddressingMode::ZeroPage,
+        length: 2,
+        cycles: 5,
     }),
-    None,
     Some(Instruction {
         opcode: OpCode::CLI,
         mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
     }),
     Some(Instruction {
         opcode: OpCode::EOR,
-        mode: AddressingMode::AbsoluteX,
+        mode: AddressingMode::AbsoluteY,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::PHY,
+        mode: AddressingMode::Stack,
+        length: 1,
+        cycles: 3,
+    }),
+    Some(Instruction {
+        opcode: OpCode::NOP,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 1,
+    }),
+    Some(Instruction {
+        opcode: OpCode::NOP,
+        mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 8,
     }),
-    None,
-    None,
-    None,
     Some(Instruction {
         opcode: OpCode::EOR,
         mode: AddressingMode::AbsoluteX,
+        length: 3,
+        cycles: 4,
     }),
     Some(Instruction {
         opcode: OpCode::LSR,
         mode: AddressingMode::AbsoluteX,
+        length: 3,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::BBR5,
+        mode: AddressingMode::ZeroPageRelative,
+        length: 3,
+        cycles: 5,
     }),
-    None,
+    // 0x60
     Some(Instruction {
         opcode: OpCode::RTS,
-        mode: AddressingMode::Implied,
+        mode: AddressingMode::Stack,
+        length: 1,
+        cycles: 6,
     }),
     Some(Instruction {
         opcode: OpCode::ADC,
         mode: AddressingMode::IndexedIndirectX,
+        length: 2,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::NOP,
+        mode: AddressingMode::Immediate,
+        length: 2,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::NOP,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 1,
+    }),
+    Some(Instruction {
+        opcode: OpCode::STZ,
+        mode: AddressingMode::ZeroPage,
+        length: 2,
+        cycles: 3,
     }),
-    None,
-    None,
-    None,
     Some(Instruction {
         opcode: OpCode::ADC,
         mode: AddressingMode::ZeroPage,
+        length: 2,
+        cycles: 3,
     }),
     Some(Instruction {
         opcode: OpCode::ROR,
         mode: AddressingMode::ZeroPage,
+        length: 2,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::RMB6,
+        mode: AddressingMode::ZeroPage,
+        length: 2,
+        cycles: 5,
     }),
-    None,
     Some(Instruction {
         opcode: OpCode::PLA,
-        mode: AddressingMode::Implied,
+        mode: AddressingMode::Stack,
+        length: 1,
+        cycles: 4,
     }),
     Some(Instruction {
         opcode: OpCode::ADC,
         mode: AddressingMode::Immediate,
+        length: 2,
+        cycles: 2,
     }),
     Some(Instruction {
         opcode: OpCode::ROR,
         mode: AddressingMode::Accumulator,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::NOP,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 1,
     }),
-    None,
     Some(Instruction {
         opcode: OpCode::JMP,
-        mode: AddressingMode::Indirect,
+        mode: AddressingMode::AbsoluteIndirect,
+        length: 3,
+        cycles: 6,
     }),
     Some(Instruction {
         opcode: OpCode::ADC,
         mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 4,
     }),
     Some(Instruction {
         opcode: OpCode::ROR,
         mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::BBR6,
+        mode: AddressingMode::ZeroPageRelative,
+        length: 3,
+        cycles: 5,
     }),
-    None,
+    // 0x70
     Some(Instruction {
         opcode: OpCode::BVS,
         mode: AddressingMode::Relative,
+        length: 2,
+        cycles: 2,
     }),
     Some(Instruction {
         opcode: OpCode::ADC,
         mode: AddressingMode::IndirectIndexedY,
+        length: 2,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ADC,
+        mode: AddressingMode::Indirect,
+        length: 2,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::NOP,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 1,
+    }),
+    Some(Instruction {
+        opcode: OpCode::STZ,
+        mode: AddressingMode::ZeroPageX,
+        length: 2,
+        cycles: 4,
     }),
-    None,
-    None,
-    None,
     Some(Instruction {
         opcode: OpCode::ADC,
         mode: AddressingMode::ZeroPageX,
+        length: 2,
+        cycles: 4,
     }),
     Some(Instruction {
         opcode: OpCode::ROR,
         mode: AddressingMode::ZeroPageX,
+        length: 2,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::RMB7,
+        mode: AddressingMode::ZeroPage,
+        length: 2,
+        cycles: 5,
     }),
-    None,
     Some(Instruction {
         opcode: OpCode::SEI,
         mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
     }),
     Some(Instruction {
         opcode: OpCode::ADC,
         mode: AddressingMode::AbsoluteY,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::PLY,
+        mode: AddressingMode::Stack,
+        length: 1,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::NOP,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 1,
+    }),
+    Some(Instruction {
+        opcode: OpCode::JMP,
+        mode: AddressingMode::AbsoluteIndexedIndirect,
+        length: 3,
+        cycles: 6,
     }),
-    None,
-    None,
-    None,
     Some(Instruction {
         opcode: OpCode::ADC,
         mode: AddressingMode::AbsoluteX,
+        length: 3,
+        cycles: 4,
     }),
     Some(Instruction {
         opcode: OpCode::ROR,
-        mode: AddressingMode::Implied,
+        mode: AddressingMode::AbsoluteX,
+        length: 3,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::BBR7,
+        mode: AddressingMode::ZeroPageRelative,
+        length: 3,
+        cycles: 5,
+    }),
+    // 0x80
+    Some(Instruction {
+        opcode: OpCode::BRA,
+        mode: AddressingMode::Relative,
+        length: 2,
+        cycles: 3,
     }),
-    None,
-    None,
     Some(Instruction {
         opcode: OpCode::STA,
         mode: AddressingMode::IndexedIndirectX,
+        length: 2,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::NOP,
+        mode: AddressingMode::Immediate,
+        length: 2,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::NOP,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 1,
     }),
-    None,
-    None,
     Some(Instruction {
         opcode: OpCode::STY,
         mode: AddressingMode::ZeroPage,
+        length: 2,
+        cycles: 3,
     }),
     Some(Instruction {
         opcode: OpCode::STA,
         mode: AddressingMode::ZeroPage,
+        length: 2,
+        cycles: 3,
     }),
     Some(Instruction {
         opcode: OpCode::STX,
         mode: AddressingMode::ZeroPage,
+        length: 2,
+        cycles: 3,
+    }),
+    Some(Instruction {
+        opcode: OpCode::SMB0,
+        mode: AddressingMode::ZeroPage,
+        length: 2,
+        cycles: 5,
     }),
-    None,
     Some(Instruction {
         opcode: OpCode::DEY,
         mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::BIT,
+        mode: AddressingMode::Immediate,
+        length: 2,
+        cycles: 2,
     }),
-    None,
     Some(Instruction {
         opcode: OpCode::TXA,
         mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::NOP,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 1,
     }),
-    None,
     Some(Instruction {
         opcode: OpCode::STY,
         mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 4,
     }),
     Some(Instruction {
-        opcode: OpCode::STY,
+        opcode: OpCode::STA,
         mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 4,
     }),
     Some(Instruction {
         opcode: OpCode::STX,
         mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::BBS0,
+        mode: AddressingMode::ZeroPageRelative,
+        length: 3,
+        cycles: 5,
     }),
-    None,
+    // 0x90
     Some(Instruction {
         opcode: OpCode::BCC,
         mode: AddressingMode::Relative,
+        length: 2,
+        cycles: 2,
     }),
     Some(Instruction {
         opcode: OpCode::STA,
         mode: AddressingMode::IndirectIndexedY,
+        length: 2,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::STA,
+        mode: AddressingMode::Indirect,
+        length: 2,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::NOP,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 1,
     }),
-    None,
-    None,
     Some(Instruction {
         opcode: OpCode::STY,
         mode: AddressingMode::ZeroPageX,
+        length: 2,
+        cycles: 4,
     }),
     Some(Instruction {
         opcode: OpCode::STA,
         mode: AddressingMode::ZeroPageX,
+        length: 2,
+        cycles: 4,
     }),
     Some(Instruction {
         opcode: OpCode::STX,
         mode: AddressingMode::ZeroPageY,
+        length: 2,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::SMB1,
+        mode: AddressingMode::ZeroPage,
+        length: 2,
+        cycles: 5,
     }),
-    None,
     Some(Instruction {
         opcode: OpCode::TYA,
         mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
     }),
     Some(Instruction {
         opcode: OpCode::STA,
         mode: AddressingMode::AbsoluteY,
+        length: 3,
+        cycles: 5,
     }),
     Some(Instruction {
         opcode: OpCode::TXS,
         mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::NOP,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 1,
+    }),
+    Some(Instruction {
+        opcode: OpCode::STZ,
+        mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 4,
     }),
-    None,
-    None,
     Some(Instruction {
         opcode: OpCode::STA,
         mode: AddressingMode::AbsoluteX,
+        length: 3,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::STZ,
+        mode: AddressingMode::AbsoluteX,
+        length: 3,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::BBS1,
+        mode: AddressingMode::ZeroPageRelative,
+        length: 3,
+        cycles: 5,
     }),
-    None,
-    None,
+    // 0xa0
     Some(Instruction {
         opcode: OpCode::LDY,
         mode: AddressingMode::Immediate,
+        length: 2,
+        cycles: 2,
     }),
     Some(Instruction {
         opcode: OpCode::LDA,
-        mode: AddressingMode::IndirectIndexedY,
+        mode: AddressingMode::IndexedIndirectX,
+        length: 2,
+        cycles: 6,
     }),
     Some(Instruction {
         opcode: OpCode::LDX,
         mode: AddressingMode::Immediate,
+        length: 2,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::NOP,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 1,
     }),
-    None,
     Some(Instruction {
         opcode: OpCode::LDY,
         mode: AddressingMode::ZeroPage,
+        length: 2,
+        cycles: 3,
     }),
     Some(Instruction {
         opcode: OpCode::LDA,
         mode: AddressingMode::ZeroPage,
+        length: 2,
+        cycles: 3,
     }),
     Some(Instruction {
         opcode: OpCode::LDX,
         mode: AddressingMode::ZeroPage,
+        length: 2,
+        cycles: 3,
+    }),
+    Some(Instruction {
+        opcode: OpCode::SMB2,
+        mode: AddressingMode::ZeroPage,
+        length: 2,
+        cycles: 5,
     }),
-    None,
     Some(Instruction {
         opcode: OpCode::TAY,
         mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
     }),
     Some(Instruction {
         opcode: OpCode::LDA,
         mode: AddressingMode::Immediate,
+        length: 2,
+        cycles: 2,
     }),
     Some(Instruction {
         opcode: OpCode::TAX,
         mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::NOP,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 1,
     }),
-    None,
     Some(Instruction {
         opcode: OpCode::LDY,
         mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 4,
     }),
     Some(Instruction {
         opcode: OpCode::LDA,
         mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 4,
     }),
     Some(Instruction {
         opcode: OpCode::LDX,
         mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 4,
     }),
-    None,
+    Some(Instruction {
+        opcode: OpCode::BBS2,
+        mode: AddressingMode::ZeroPageRelative,
+        length: 3,
+        cycles: 5,
+    }),
+    // 0xb0
     Some(Instruction {
         opcode: OpCode::BCS,
         mode: AddressingMode::Relative,
+        length: 2,
+        cycles: 2,
     }),
     Some(Instruction {
         opcode: OpCode::LDA,
         mode: AddressingMode::IndirectIndexedY,
+        length: 2,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::LDA,
+        mode: AddressingMode::Indirect,
+        length: 2,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::NOP,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 1,
     }),
-    None,
-    None,
     Some(Instruction {
         opcode: OpCode::LDY,
         mode: AddressingMode::ZeroPageX,
+        length: 2,
+        cycles: 4,
     }),
     Some(Instruction {
         opcode: OpCode::LDA,
         mode: AddressingMode::ZeroPageX,
+        length: 2,
+        cycles: 4,
     }),
     Some(Instruction {
         opcode: OpCode::LDX,
         mode: AddressingMode::ZeroPageY,
+        length: 2,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::SMB3,
+        mode: AddressingMode::ZeroPage,
+        length: 2,
+        cycles: 5,
     }),
-    None,
     Some(Instruction {
         opcode: OpCode::CLV,
         mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
     }),
     Some(Instruction {
         opcode: OpCode::LDA,
         mode: AddressingMode::AbsoluteY,
+        length: 3,
+        cycles: 4,
     }),
     Some(Instruction {
         opcode: OpCode::TSX,
         mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::NOP,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 1,
     }),
-    None,
     Some(Instruction {
         opcode: OpCode::LDY,
         mode: AddressingMode::AbsoluteX,
+        length: 3,
+        cycles: 4,
     }),
     Some(Instruction {
         opcode: OpCode::LDA,
         mode: AddressingMode::AbsoluteX,
+        length: 3,
+        cycles: 4,
     }),
     Some(Instruction {
         opcode: OpCode::LDX,
         mode: AddressingMode::AbsoluteY,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::BBS3,
+        mode: AddressingMode::ZeroPageRelative,
+        length: 3,
+        cycles: 5,
     }),
-    None,
+    // 0xc0
     Some(Instruction {
         opcode: OpCode::CPY,
         mode: AddressingMode::Immediate,
+        length: 2,
+        cycles: 2,
     }),
     Some(Instruction {
         opcode: OpCode::CMP,
         mode: AddressingMode::IndexedIndirectX,
+        length: 2,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::NOP,
+        mode: AddressingMode::Immediate,
+        length: 2,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::NOP,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 1,
     }),
-    None,
-    None,
     Some(Instruction {
         opcode: OpCode::CPY,
         mode: AddressingMode::ZeroPage,
+        length: 2,
+        cycles: 3,
     }),
     Some(Instruction {
         opcode: OpCode::CMP,
         mode: AddressingMode::ZeroPage,
+        length: 2,
+        cycles: 3,
     }),
     Some(Instruction {
         opcode: OpCode::DEC,
         mode: AddressingMode::ZeroPage,
+        length: 2,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::SMB4,
+        mode: AddressingMode::ZeroPage,
+        length: 2,
+        cycles: 5,
     }),
-    None,
     Some(Instruction {
         opcode: OpCode::INY,
         mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
     }),
     Some(Instruction {
         opcode: OpCode::CMP,
         mode: AddressingMode::Immediate,
+        length: 2,
+        cycles: 2,
     }),
     Some(Instruction {
         opcode: OpCode::DEX,
         mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::WAI,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 3,
     }),
-    None,
     Some(Instruction {
         opcode: OpCode::CPY,
         mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 4,
     }),
     Some(Instruction {
         opcode: OpCode::CMP,
         mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 4,
     }),
     Some(Instruction {
         opcode: OpCode::DEC,
         mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::BBS4,
+        mode: AddressingMode::ZeroPageRelative,
+        length: 3,
+        cycles: 5,
     }),
-    None,
+    // 0xd0
     Some(Instruction {
         opcode: OpCode::BNE,
         mode: AddressingMode::Relative,
+        length: 2,
+        cycles: 2,
     }),
     Some(Instruction {
         opcode: OpCode::CMP,
         mode: AddressingMode::IndirectIndexedY,
+        length: 2,
+        cycles: 5,
     }),
-    None,
-    None,
-    None,
     Some(Instruction {
         opcode: OpCode::CMP,
-        mode: AddressingMode::ZeroPageX,
+        mode: AddressingMode::Indirect,
+        length: 2,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::NOP,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 1,
+    }),
+    Some(Instruction {
+        opcode: OpCode::NOP,
+        mode: AddressingMode::ZeroPageX,
+        length: 2,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::CMP,
+        mode: AddressingMode::ZeroPageX,
+        length: 2,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::DEC,
+        mode: AddressingMode::ZeroPageX,
+        length: 2,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::SMB5,
+        mode: AddressingMode::ZeroPage,
+        length: 2,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::CLD,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::CMP,
+        mode: AddressingMode::AbsoluteY,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::PHX,
+        mode: AddressingMode::Stack,
+        length: 1,
+        cycles: 3,
+    }),
+    Some(Instruction {
+        opcode: OpCode::STP,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 3,
+    }),
+    Some(Instruction {
+        opcode: OpCode::NOP,
+        mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::CMP,
+        mode: AddressingMode::AbsoluteX,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::DEC,
+        mode: AddressingMode::AbsoluteX,
+        length: 3,
+        cycles: 7,
+    }),
+    Some(Instruction {
+        opcode: OpCode::BBS5,
+        mode: AddressingMode::ZeroPageRelative,
+        length: 3,
+        cycles: 5,
+    }),
+    // 0xe0
+    Some(Instruction {
+        opcode: OpCode::CPX,
+        mode: AddressingMode::Immediate,
+        length: 2,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::SBC,
+        mode: AddressingMode::IndexedIndirectX,
+        length: 2,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::NOP,
+        mode: AddressingMode::Immediate,
+        length: 2,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::NOP,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 1,
+    }),
+    Some(Instruction {
+        opcode: OpCode::CPX,
+        mode: AddressingMode::ZeroPage,
+        length: 2,
+        cycles: 3,
+    }),
+    Some(Instruction {
+        opcode: OpCode::SBC,
+        mode: AddressingMode::ZeroPage,
+        length: 2,
+        cycles: 3,
+    }),
+    Some(Instruction {
+        opcode: OpCode::INC,
+        mode: AddressingMode::ZeroPage,
+        length: 2,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::SMB6,
+        mode: AddressingMode::ZeroPage,
+        length: 2,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::INX,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::SBC,
+        mode: AddressingMode::Immediate,
+        length: 2,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::NOP,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::NOP,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 1,
+    }),
+    Some(Instruction {
+        opcode: OpCode::CPX,
+        mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::SBC,
+        mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::INC,
+        mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::BBS6,
+        mode: AddressingMode::ZeroPageRelative,
+        length: 3,
+        cycles: 5,
+    }),
+    // 0xf0
+    Some(Instruction {
+        opcode: OpCode::BEQ,
+        mode: AddressingMode::Relative,
+        length: 2,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::SBC,
+        mode: AddressingMode::IndirectIndexedY,
+        length: 2,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::SBC,
+        mode: AddressingMode::Indirect,
+        length: 2,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::NOP,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 1,
+    }),
+    Some(Instruction {
+        opcode: OpCode::NOP,
+        mode: AddressingMode::ZeroPageX,
+        length: 2,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::SBC,
+        mode: AddressingMode::ZeroPageX,
+        length: 2,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::INC,
+        mode: AddressingMode::ZeroPageX,
+        length: 2,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::SMB7,
+        mode: AddressingMode::ZeroPage,
+        length: 2,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::SED,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::SBC,
+        mode: AddressingMode::AbsoluteY,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::PLX,
+        mode: AddressingMode::Stack,
+        length: 1,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::NOP,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 1,
+    }),
+    Some(Instruction {
+        opcode: OpCode::NOP,
+        mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::SBC,
+        mode: AddressingMode::AbsoluteX,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::INC,
+        mode: AddressingMode::AbsoluteX,
+        length: 3,
+        cycles: 7,
+    }),
+    Some(Instruction {
+        opcode: OpCode::BBS7,
+        mode: AddressingMode::ZeroPageRelative,
+        length: 3,
+        cycles: 5,
+    }),
+];
+
+/// Decode table of the original NMOS 6502, including the undocumented
+/// opcodes.
+pub static NMOS_OP_CODES: [Option<Instruction>; 256] = [
+    // 0x00
+    Some(Instruction {
+        opcode: OpCode::BRK,
+        mode: AddressingMode::Stack,
+        length: 2,
+        cycles: 7,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ORA,
+        mode: AddressingMode::IndexedIndirectX,
+        length: 2,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::JAM,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::SLO,
+        mode: AddressingMode::IndexedIndirectX,
+        length: 2,
+        cycles: 8,
+    }),
+    Some(Instruction {
+        opcode: OpCode::NOP,
+        mode: AddressingMode::ZeroPage,
+        length: 2,
+        cycles: 3,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ORA,
+        mode: AddressingMode::ZeroPage,
+        length: 2,
+        cycles: 3,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ASL,
+        mode: AddressingMode::ZeroPage,
+        length: 2,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::SLO,
+        mode: AddressingMode::ZeroPage,
+        length: 2,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::PHP,
+        mode: AddressingMode::Stack,
+        length: 1,
+        cycles: 3,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ORA,
+        mode: AddressingMode::Immediate,
+        length: 2,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ASL,
+        mode: AddressingMode::Accumulator,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ANC,
+        mode: AddressingMode::Immediate,
+        length: 2,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::NOP,
+        mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ORA,
+        mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ASL,
+        mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::SLO,
+        mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 6,
+    }),
+    // 0x10
+    Some(Instruction {
+        opcode: OpCode::BPL,
+        mode: AddressingMode::Relative,
+        length: 2,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ORA,
+        mode: AddressingMode::IndirectIndexedY,
+        length: 2,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::JAM,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::SLO,
+        mode: AddressingMode::IndirectIndexedY,
+        length: 2,
+        cycles: 8,
+    }),
+    Some(Instruction {
+        opcode: OpCode::NOP,
+        mode: AddressingMode::ZeroPageX,
+        length: 2,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ORA,
+        mode: AddressingMode::ZeroPageX,
+        length: 2,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ASL,
+        mode: AddressingMode::ZeroPageX,
+        length: 2,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::SLO,
+        mode: AddressingMode::ZeroPageX,
+        length: 2,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::CLC,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ORA,
+        mode: AddressingMode::AbsoluteY,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::NOP,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::SLO,
+        mode: AddressingMode::AbsoluteY,
+        length: 3,
+        cycles: 7,
+    }),
+    Some(Instruction {
+        opcode: OpCode::NOP,
+        mode: AddressingMode::AbsoluteX,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ORA,
+        mode: AddressingMode::AbsoluteX,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ASL,
+        mode: AddressingMode::AbsoluteX,
+        length: 3,
+        cycles: 7,
+    }),
+    Some(Instruction {
+        opcode: OpCode::SLO,
+        mode: AddressingMode::AbsoluteX,
+        length: 3,
+        cycles: 7,
+    }),
+    // 0x20
+    Some(Instruction {
+        opcode: OpCode::JSR,
+        mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::AND,
+        mode: AddressingMode::IndexedIndirectX,
+        length: 2,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::JAM,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::RLA,
+        mode: AddressingMode::IndexedIndirectX,
+        length: 2,
+        cycles: 8,
+    }),
+    Some(Instruction {
+        opcode: OpCode::BIT,
+        mode: AddressingMode::ZeroPage,
+        length: 2,
+        cycles: 3,
+    }),
+    Some(Instruction {
+        opcode: OpCode::AND,
+        mode: AddressingMode::ZeroPage,
+        length: 2,
+        cycles: 3,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ROL,
+        mode: AddressingMode::ZeroPage,
+        length: 2,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::RLA,
+        mode: AddressingMode::ZeroPage,
+        length: 2,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::PLP,
+        mode: AddressingMode::Stack,
+        length: 1,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::AND,
+        mode: AddressingMode::Immediate,
+        length: 2,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ROL,
+        mode: AddressingMode::Accumulator,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ANC,
+        mode: AddressingMode::Immediate,
+        length: 2,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::BIT,
+        mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::AND,
+        mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ROL,
+        mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::RLA,
+        mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 6,
+    }),
+    // 0x30
+    Some(Instruction {
+        opcode: OpCode::BMI,
+        mode: AddressingMode::Relative,
+        length: 2,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::AND,
+        mode: AddressingMode::IndirectIndexedY,
+        length: 2,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::JAM,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::RLA,
+        mode: AddressingMode::IndirectIndexedY,
+        length: 2,
+        cycles: 8,
+    }),
+    Some(Instruction {
+        opcode: OpCode::NOP,
+        mode: AddressingMode::ZeroPageX,
+        length: 2,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::AND,
+        mode: AddressingMode::ZeroPageX,
+        length: 2,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ROL,
+        mode: AddressingMode::ZeroPageX,
+        length: 2,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::RLA,
+        mode: AddressingMode::ZeroPageX,
+        length: 2,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::SEC,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::AND,
+        mode: AddressingMode::AbsoluteY,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::NOP,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::RLA,
+        mode: AddressingMode::AbsoluteY,
+        length: 3,
+        cycles: 7,
+    }),
+    Some(Instruction {
+        opcode: OpCode::NOP,
+        mode: AddressingMode::AbsoluteX,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::AND,
+        mode: AddressingMode::AbsoluteX,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ROL,
+        mode: AddressingMode::AbsoluteX,
+        length: 3,
+        cycles: 7,
+    }),
+    Some(Instruction {
+        opcode: OpCode::RLA,
+        mode: AddressingMode::AbsoluteX,
+        length: 3,
+        cycles: 7,
+    }),
+    // 0x40
+    Some(Instruction {
+        opcode: OpCode::RTI,
+        mode: AddressingMode::Stack,
+        length: 1,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::EOR,
+        mode: AddressingMode::IndexedIndirectX,
+        length: 2,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::JAM,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::SRE,
+        mode: AddressingMode::IndexedIndirectX,
+        length: 2,
+        cycles: 8,
+    }),
+    Some(Instruction {
+        opcode: OpCode::NOP,
+        mode: AddressingMode::ZeroPage,
+        length: 2,
+        cycles: 3,
+    }),
+    Some(Instruction {
+        opcode: OpCode::EOR,
+        mode: AddressingMode::ZeroPage,
+        length: 2,
+        cycles: 3,
+    }),
+    Some(Instruction {
+        opcode: OpCode::LSR,
+        mode: AddressingMode::ZeroPage,
+        length: 2,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::SRE,
+        mode: AddressingMode::ZeroPage,
+        length: 2,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::PHA,
+        mode: AddressingMode::Stack,
+        length: 1,
+        cycles: 3,
+    }),
+    Some(Instruction {
+        opcode: OpCode::EOR,
+        mode: AddressingMode::Immediate,
+        length: 2,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::LSR,
+        mode: AddressingMode::Accumulator,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ALR,
+        mode: AddressingMode::Immediate,
+        length: 2,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::JMP,
+        mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 3,
+    }),
+    Some(Instruction {
+        opcode: OpCode::EOR,
+        mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::LSR,
+        mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::SRE,
+        mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 6,
+    }),
+    // 0x50
+    Some(Instruction {
+        opcode: OpCode::BVC,
+        mode: AddressingMode::Relative,
+        length: 2,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::EOR,
+        mode: AddressingMode::IndirectIndexedY,
+        length: 2,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::JAM,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::SRE,
+        mode: AddressingMode::IndirectIndexedY,
+        length: 2,
+        cycles: 8,
+    }),
+    Some(Instruction {
+        opcode: OpCode::NOP,
+        mode: AddressingMode::ZeroPageX,
+        length: 2,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::EOR,
+        mode: AddressingMode::ZeroPageX,
+        length: 2,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::LSR,
+        mode: AddressingMode::ZeroPageX,
+        length: 2,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::SRE,
+        mode: AddressingMode::ZeroPageX,
+        length: 2,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::CLI,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::EOR,
+        mode: AddressingMode::AbsoluteY,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::NOP,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::SRE,
+        mode: AddressingMode::AbsoluteY,
+        length: 3,
+        cycles: 7,
+    }),
+    Some(Instruction {
+        opcode: OpCode::NOP,
+        mode: AddressingMode::AbsoluteX,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::EOR,
+        mode: AddressingMode::AbsoluteX,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::LSR,
+        mode: AddressingMode::AbsoluteX,
+        length: 3,
+        cycles: 7,
+    }),
+    Some(Instruction {
+        opcode: OpCode::SRE,
+        mode: AddressingMode::AbsoluteX,
+        length: 3,
+        cycles: 7,
+    }),
+    // 0x60
+    Some(Instruction {
+        opcode: OpCode::RTS,
+        mode: AddressingMode::Stack,
+        length: 1,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ADC,
+        mode: AddressingMode::IndexedIndirectX,
+        length: 2,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::JAM,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::RRA,
+        mode: AddressingMode::IndexedIndirectX,
+        length: 2,
+        cycles: 8,
+    }),
+    Some(Instruction {
+        opcode: OpCode::NOP,
+        mode: AddressingMode::ZeroPage,
+        length: 2,
+        cycles: 3,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ADC,
+        mode: AddressingMode::ZeroPage,
+        length: 2,
+        cycles: 3,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ROR,
+        mode: AddressingMode::ZeroPage,
+        length: 2,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::RRA,
+        mode: AddressingMode::ZeroPage,
+        length: 2,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::PLA,
+        mode: AddressingMode::Stack,
+        length: 1,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ADC,
+        mode: AddressingMode::Immediate,
+        length: 2,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ROR,
+        mode: AddressingMode::Accumulator,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ARR,
+        mode: AddressingMode::Immediate,
+        length: 2,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::JMP,
+        mode: AddressingMode::AbsoluteIndirect,
+        length: 3,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ADC,
+        mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ROR,
+        mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::RRA,
+        mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 6,
+    }),
+    // 0x70
+    Some(Instruction {
+        opcode: OpCode::BVS,
+        mode: AddressingMode::Relative,
+        length: 2,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ADC,
+        mode: AddressingMode::IndirectIndexedY,
+        length: 2,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::JAM,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::RRA,
+        mode: AddressingMode::IndirectIndexedY,
+        length: 2,
+        cycles: 8,
+    }),
+    Some(Instruction {
+        opcode: OpCode::NOP,
+        mode: AddressingMode::ZeroPageX,
+        length: 2,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ADC,
+        mode: AddressingMode::ZeroPageX,
+        length: 2,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ROR,
+        mode: AddressingMode::ZeroPageX,
+        length: 2,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::RRA,
+        mode: AddressingMode::ZeroPageX,
+        length: 2,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::SEI,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ADC,
+        mode: AddressingMode::AbsoluteY,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::NOP,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::RRA,
+        mode: AddressingMode::AbsoluteY,
+        length: 3,
+        cycles: 7,
+    }),
+    Some(Instruction {
+        opcode: OpCode::NOP,
+        mode: AddressingMode::AbsoluteX,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ADC,
+        mode: AddressingMode::AbsoluteX,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ROR,
+        mode: AddressingMode::AbsoluteX,
+        length: 3,
+        cycles: 7,
+    }),
+    Some(Instruction {
+        opcode: OpCode::RRA,
+        mode: AddressingMode::AbsoluteX,
+        length: 3,
+        cycles: 7,
+    }),
+    // 0x80
+    Some(Instruction {
+        opcode: OpCode::NOP,
+        mode: AddressingMode::Immediate,
+        length: 2,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::STA,
+        mode: AddressingMode::IndexedIndirectX,
+        length: 2,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::NOP,
+        mode: AddressingMode::Immediate,
+        length: 2,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::SAX,
+        mode: AddressingMode::IndexedIndirectX,
+        length: 2,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::STY,
+        mode: AddressingMode::ZeroPage,
+        length: 2,
+        cycles: 3,
+    }),
+    Some(Instruction {
+        opcode: OpCode::STA,
+        mode: AddressingMode::ZeroPage,
+        length: 2,
+        cycles: 3,
+    }),
+    Some(Instruction {
+        opcode: OpCode::STX,
+        mode: AddressingMode::ZeroPage,
+        length: 2,
+        cycles: 3,
+    }),
+    Some(Instruction {
+        opcode: OpCode::SAX,
+        mode: AddressingMode::ZeroPage,
+        length: 2,
+        cycles: 3,
+    }),
+    Some(Instruction {
+        opcode: OpCode::DEY,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::NOP,
+        mode: AddressingMode::Immediate,
+        length: 2,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::TXA,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ANE,
+        mode: AddressingMode::Immediate,
+        length: 2,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::STY,
+        mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::STA,
+        mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::STX,
+        mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::SAX,
+        mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 4,
+    }),
+    // 0x90
+    Some(Instruction {
+        opcode: OpCode::BCC,
+        mode: AddressingMode::Relative,
+        length: 2,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::STA,
+        mode: AddressingMode::IndirectIndexedY,
+        length: 2,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::JAM,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::SHA,
+        mode: AddressingMode::IndirectIndexedY,
+        length: 2,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::STY,
+        mode: AddressingMode::ZeroPageX,
+        length: 2,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::STA,
+        mode: AddressingMode::ZeroPageX,
+        length: 2,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::STX,
+        mode: AddressingMode::ZeroPageY,
+        length: 2,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::SAX,
+        mode: AddressingMode::ZeroPageY,
+        length: 2,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::TYA,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::STA,
+        mode: AddressingMode::AbsoluteY,
+        length: 3,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::TXS,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::TAS,
+        mode: AddressingMode::AbsoluteY,
+        length: 3,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::SHY,
+        mode: AddressingMode::AbsoluteX,
+        length: 3,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::STA,
+        mode: AddressingMode::AbsoluteX,
+        length: 3,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::SHX,
+        mode: AddressingMode::AbsoluteY,
+        length: 3,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::SHA,
+        mode: AddressingMode::AbsoluteY,
+        length: 3,
+        cycles: 5,
+    }),
+    // 0xa0
+    Some(Instruction {
+        opcode: OpCode::LDY,
+        mode: AddressingMode::Immediate,
+        length: 2,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::LDA,
+        mode: AddressingMode::IndexedIndirectX,
+        length: 2,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::LDX,
+        mode: AddressingMode::Immediate,
+        length: 2,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::LAX,
+        mode: AddressingMode::IndexedIndirectX,
+        length: 2,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::LDY,
+        mode: AddressingMode::ZeroPage,
+        length: 2,
+        cycles: 3,
+    }),
+    Some(Instruction {
+        opcode: OpCode::LDA,
+        mode: AddressingMode::ZeroPage,
+        length: 2,
+        cycles: 3,
+    }),
+    Some(Instruction {
+        opcode: OpCode::LDX,
+        mode: AddressingMode::ZeroPage,
+        length: 2,
+        cycles: 3,
+    }),
+    Some(Instruction {
+        opcode: OpCode::LAX,
+        mode: AddressingMode::ZeroPage,
+        length: 2,
+        cycles: 3,
+    }),
+    Some(Instruction {
+        opcode: OpCode::TAY,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::LDA,
+        mode: AddressingMode::Immediate,
+        length: 2,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::TAX,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::LXA,
+        mode: AddressingMode::Immediate,
+        length: 2,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::LDY,
+        mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::LDA,
+        mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::LDX,
+        mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::LAX,
+        mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 4,
+    }),
+    // 0xb0
+    Some(Instruction {
+        opcode: OpCode::BCS,
+        mode: AddressingMode::Relative,
+        length: 2,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::LDA,
+        mode: AddressingMode::IndirectIndexedY,
+        length: 2,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::JAM,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::LAX,
+        mode: AddressingMode::IndirectIndexedY,
+        length: 2,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::LDY,
+        mode: AddressingMode::ZeroPageX,
+        length: 2,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::LDA,
+        mode: AddressingMode::ZeroPageX,
+        length: 2,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::LDX,
+        mode: AddressingMode::ZeroPageY,
+        length: 2,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::LAX,
+        mode: AddressingMode::ZeroPageY,
+        length: 2,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::CLV,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::LDA,
+        mode: AddressingMode::AbsoluteY,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::TSX,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::LAS,
+        mode: AddressingMode::AbsoluteY,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::LDY,
+        mode: AddressingMode::AbsoluteX,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::LDA,
+        mode: AddressingMode::AbsoluteX,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::LDX,
+        mode: AddressingMode::AbsoluteY,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::LAX,
+        mode: AddressingMode::AbsoluteY,
+        length: 3,
+        cycles: 4,
+    }),
+    // 0xc0
+    Some(Instruction {
+        opcode: OpCode::CPY,
+        mode: AddressingMode::Immediate,
+        length: 2,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::CMP,
+        mode: AddressingMode::IndexedIndirectX,
+        length: 2,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::NOP,
+        mode: AddressingMode::Immediate,
+        length: 2,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::DCP,
+        mode: AddressingMode::IndexedIndirectX,
+        length: 2,
+        cycles: 8,
+    }),
+    Some(Instruction {
+        opcode: OpCode::CPY,
+        mode: AddressingMode::ZeroPage,
+        length: 2,
+        cycles: 3,
+    }),
+    Some(Instruction {
+        opcode: OpCode::CMP,
+        mode: AddressingMode::ZeroPage,
+        length: 2,
+        cycles: 3,
+    }),
+    Some(Instruction {
+        opcode: OpCode::DEC,
+        mode: AddressingMode::ZeroPage,
+        length: 2,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::DCP,
+        mode: AddressingMode::ZeroPage,
+        length: 2,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::INY,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::CMP,
+        mode: AddressingMode::Immediate,
+        length: 2,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::DEX,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::SBX,
+        mode: AddressingMode::Immediate,
+        length: 2,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::CPY,
+        mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::CMP,
+        mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::DEC,
+        mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::DCP,
+        mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 6,
+    }),
+    // 0xd0
+    Some(Instruction {
+        opcode: OpCode::BNE,
+        mode: AddressingMode::Relative,
+        length: 2,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::CMP,
+        mode: AddressingMode::IndirectIndexedY,
+        length: 2,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::JAM,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::DCP,
+        mode: AddressingMode::IndirectIndexedY,
+        length: 2,
+        cycles: 8,
+    }),
+    Some(Instruction {
+        opcode: OpCode::NOP,
+        mode: AddressingMode::ZeroPageX,
+        length: 2,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::CMP,
+        mode: AddressingMode::ZeroPageX,
+        length: 2,
+        cycles: 4,
     }),
     Some(Instruction {
         opcode: OpCode::DEC,
         mode: AddressingMode::ZeroPageX,
+        length: 2,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::DCP,
+        mode: AddressingMode::ZeroPageX,
+        length: 2,
+        cycles: 6,
     }),
-    None,
     Some(Instruction {
         opcode: OpCode::CLD,
         mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
     }),
     Some(Instruction {
         opcode: OpCode::CMP,
         mode: AddressingMode::AbsoluteY,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::NOP,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::DCP,
+        mode: AddressingMode::AbsoluteY,
+        length: 3,
+        cycles: 7,
+    }),
+    Some(Instruction {
+        opcode: OpCode::NOP,
+        mode: AddressingMode::AbsoluteX,
+        length: 3,
+        cycles: 4,
     }),
-    None,
-    None,
-    None,
     Some(Instruction {
         opcode: OpCode::CMP,
         mode: AddressingMode::AbsoluteX,
+        length: 3,
+        cycles: 4,
     }),
     Some(Instruction {
         opcode: OpCode::DEC,
         mode: AddressingMode::AbsoluteX,
+        length: 3,
+        cycles: 7,
+    }),
+    Some(Instruction {
+        opcode: OpCode::DCP,
+        mode: AddressingMode::AbsoluteX,
+        length: 3,
+        cycles: 7,
     }),
-    None,
+    // 0xe0
     Some(Instruction {
         opcode: OpCode::CPX,
         mode: AddressingMode::Immediate,
+        length: 2,
+        cycles: 2,
     }),
     Some(Instruction {
         opcode: OpCode::SBC,
         mode: AddressingMode::IndexedIndirectX,
+        length: 2,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::NOP,
+        mode: AddressingMode::Immediate,
+        length: 2,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ISB,
+        mode: AddressingMode::IndexedIndirectX,
+        length: 2,
+        cycles: 8,
     }),
-    None,
-    None,
     Some(Instruction {
         opcode: OpCode::CPX,
         mode: AddressingMode::ZeroPage,
+        length: 2,
+        cycles: 3,
     }),
     Some(Instruction {
         opcode: OpCode::SBC,
         mode: AddressingMode::ZeroPage,
+        length: 2,
+        cycles: 3,
     }),
     Some(Instruction {
         opcode: OpCode::INC,
         mode: AddressingMode::ZeroPage,
+        length: 2,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ISB,
+        mode: AddressingMode::ZeroPage,
+        length: 2,
+        cycles: 5,
     }),
-    None,
     Some(Instruction {
         opcode: OpCode::INX,
         mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
     }),
     Some(Instruction {
         opcode: OpCode::SBC,
         mode: AddressingMode::Immediate,
+        length: 2,
+        cycles: 2,
     }),
     Some(Instruction {
         opcode: OpCode::NOP,
         mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::SBC,
+        mode: AddressingMode::Immediate,
+        length: 2,
+        cycles: 2,
     }),
-    None,
     Some(Instruction {
         opcode: OpCode::CPX,
         mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 4,
     }),
     Some(Instruction {
         opcode: OpCode::SBC,
         mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 4,
     }),
     Some(Instruction {
         opcode: OpCode::INC,
         mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ISB,
+        mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 6,
     }),
-    None,
+    // 0xf0
     Some(Instruction {
         opcode: OpCode::BEQ,
         mode: AddressingMode::Relative,
+        length: 2,
+        cycles: 2,
     }),
     Some(Instruction {
         opcode: OpCode::SBC,
         mode: AddressingMode::IndirectIndexedY,
+        length: 2,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::JAM,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ISB,
+        mode: AddressingMode::IndirectIndexedY,
+        length: 2,
+        cycles: 8,
+    }),
+    Some(Instruction {
+        opcode: OpCode::NOP,
+        mode: AddressingMode::ZeroPageX,
+        length: 2,
+        cycles: 4,
     }),
-    None,
-    None,
-    None,
     Some(Instruction {
         opcode: OpCode::SBC,
         mode: AddressingMode::ZeroPageX,
+        length: 2,
+        cycles: 4,
     }),
     Some(Instruction {
         opcode: OpCode::INC,
         mode: AddressingMode::ZeroPageX,
+        length: 2,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ISB,
+        mode: AddressingMode::ZeroPageX,
+        length: 2,
+        cycles: 6,
     }),
-    None,
     Some(Instruction {
         opcode: OpCode::SED,
         mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
     }),
     Some(Instruction {
         opcode: OpCode::SBC,
         mode: AddressingMode::AbsoluteY,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::NOP,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ISB,
+        mode: AddressingMode::AbsoluteY,
+        length: 3,
+        cycles: 7,
+    }),
+    Some(Instruction {
+        opcode: OpCode::NOP,
+        mode: AddressingMode::AbsoluteX,
+        length: 3,
+        cycles: 4,
     }),
-    None,
-    None,
-    None,
     Some(Instruction {
         opcode: OpCode::SBC,
         mode: AddressingMode::AbsoluteX,
+        length: 3,
+        cycles: 4,
     }),
     Some(Instruction {
         opcode: OpCode::INC,
         mode: AddressingMode::AbsoluteX,
+        length: 3,
+        cycles: 7,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ISB,
+        mode: AddressingMode::AbsoluteX,
+        length: 3,
+        cycles: 7,
     }),
-    None,
 ];
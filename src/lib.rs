@@ -1,6 +1,14 @@
 #![allow(unused)]
+pub mod bus;
 pub mod code;
 pub mod cpu;
+pub mod device;
+pub mod devices;
+pub mod error;
 pub mod instruction;
+pub mod map;
 pub mod memory;
 pub mod registers;
+pub mod variant;
+#[cfg(feature = "w65c816")]
+pub mod w65c816;
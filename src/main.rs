@@ -1,12 +1,147 @@
 use std::env;
-use volve::{code, cpu::Cpu};
+use std::process;
+use volve::bus::Bus;
+use volve::code::{self, Format, Image};
+use volve::cpu::Cpu;
+use volve::devices::acia::Acia;
+use volve::devices::semihost::{self, Semihost};
+use volve::devices::serial::Connection;
+use volve::map::MemoryMap;
+use volve::memory::{RAM_HIGH_ADDRESS, RAM_LOW_ADDRESS, ROM_HIGH_ADDRESS, ROM_LOW_ADDRESS};
+use volve::variant::CpuVariant;
+
+const USAGE: &str =
+    "usage: volve [--acia ADDRESS] [--serial terminal|pty|tcp:PORT] [--semihost ADDRESS] [--load ADDRESS FILE]... ROM|HEX|SREC";
+
+/// Where the ACIA sits unless told otherwise, as on the common hobby boards.
+const DEFAULT_ACIA_ADDRESS: u16 = 0x5000;
+
+fn usage() -> ! {
+    eprintln!("{}", USAGE);
+    process::exit(2);
+}
+
+/// Reports `error` and exits, for failures that are not the user's fault.
+fn fail<E: std::fmt::Display>(error: E) -> ! {
+    eprintln!("volve: {}", error);
+    process::exit(1);
+}
+
+fn parse_address(value: &str) -> u16 {
+    let digits = value.trim_start_matches('$').trim_start_matches("0x");
+    u16::from_str_radix(digits, 16).unwrap_or_else(|_| usage())
+}
+
+fn connect(serial: &str) -> Connection {
+    let connection = match serial {
+        "terminal" => Ok(Connection::terminal()),
+        #[cfg(unix)]
+        "pty" => Connection::pty().map(|(connection, path)| {
+            eprintln!("ACIA connected to {}", path);
+            connection
+        }),
+        #[cfg(not(unix))]
+        "pty" => fail("pty is only available on unix"),
+        _ => {
+            let port = serial
+                .strip_prefix("tcp:")
+                .and_then(|port| port.parse().ok())
+                .unwrap_or_else(|| usage());
+            eprintln!("Waiting for a connection on localhost:{}", port);
+            Connection::tcp(port)
+        }
+    };
+    connection.unwrap_or_else(|error| fail(error))
+}
 
 fn main() {
-    let binary = code::read_file(env::args().nth(1).unwrap());
+    let mut rom = None;
+    let mut acia_address = DEFAULT_ACIA_ADDRESS;
+    let mut serial = None;
+    let mut semihost_address = None;
+    // images loaded on top of the ROM, such as a RAM preload
+    let mut loads = Vec::new();
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--acia" => acia_address = parse_address(&args.next().unwrap_or_else(|| usage())),
+            "--serial" => serial = Some(args.next().unwrap_or_else(|| usage())),
+            "--semihost" => {
+                semihost_address = Some(parse_address(&args.next().unwrap_or_else(|| usage())))
+            }
+            "--load" => {
+                let address = parse_address(&args.next().unwrap_or_else(|| usage()));
+                loads.push((address, args.next().unwrap_or_else(|| usage())));
+            }
+            _ if rom.is_none() => rom = Some(arg),
+            _ => usage(),
+        }
+    }
+    let rom = rom.unwrap_or_else(|| usage());
+    let acia_range = acia_address..=acia_address.checked_add(3).unwrap_or_else(|| usage());
+
+    // stdin can only feed one of the ACIA and the semihost, so with the
+    // semihost the ACIA is left out unless it goes to a PTY or TCP
+    let serial = match (serial, semihost_address) {
+        (Some(serial), Some(_)) if serial == "terminal" => {
+            fail("--serial terminal and --semihost both need stdin")
+        }
+        (None, Some(_)) => None,
+        (serial, _) => Some(serial.unwrap_or_else(|| String::from("terminal"))),
+    };
 
-    let mut cpu = Cpu::new();
+    let mut builder = MemoryMap::builder()
+        .ram(RAM_LOW_ADDRESS..=RAM_HIGH_ADDRESS)
+        .and_then(|builder| builder.rom(ROM_LOW_ADDRESS..=ROM_HIGH_ADDRESS, &[]))
+        .unwrap_or_else(|error| fail(error));
+    if let Some(serial) = serial {
+        builder = builder
+            .device(acia_range, Acia::new(connect(&serial)))
+            .unwrap_or_else(|error| fail(error));
+    }
+    if let Some(address) = semihost_address {
+        let end = address
+            .checked_add(semihost::SIZE - 1)
+            .unwrap_or_else(|| usage());
+        builder = builder
+            .device(address..=end, Semihost::new())
+            .unwrap_or_else(|error| fail(error));
+    }
+    let mut memory = builder.build();
 
-    code::upload_to_rom(&mut cpu, binary);
+    // text images say where they go, flat binaries fill the ROM from its start
+    let loaded = match Format::from_path(&rom) {
+        Some(format) => Image::read(&rom, format).and_then(|image| image.load(&mut memory)),
+        None => code::read_file(&rom).and_then(|binary| {
+            code::load_region(&mut memory, ROM_LOW_ADDRESS..=ROM_HIGH_ADDRESS, &binary)
+        }),
+    }
+    .map_err(|error| format!("{}: {}", rom, error))
+    .and_then(|()| {
+        // the address only places flat binaries, text images carry their own
+        loads.iter().try_for_each(|(address, path)| {
+            match Format::from_path(path) {
+                Some(format) => Image::read(path, format).and_then(|image| image.load(&mut memory)),
+                None => code::read_file(path)
+                    .and_then(|binary| code::load(&mut memory, *address, &binary)),
+            }
+            .map_err(|error| format!("{}: {}", path, error))
+        })
+    });
+    // process::exit skips destructors, and the ACIA has to give the
+    // terminal back, so the memory goes first
+    if let Err(error) = loaded {
+        drop(memory);
+        fail(error);
+    }
+    let mut cpu = Cpu::with_bus(memory, CpuVariant::default());
 
-    cpu.run();
+    let result = cpu.run();
+    let exit_code = cpu.bus.exit_code().unwrap_or(0);
+    drop(cpu);
+    if let Err(error) = result {
+        fail(error);
+    }
+    process::exit(exit_code as i32);
 }
@@ -0,0 +1,423 @@
+//! Declarative layout of the address space, built from RAM, ROM, mirrored,
+//! device and unmapped regions
+
+use crate::bus::Bus;
+use crate::code;
+use crate::device::{Device, Devices};
+use crate::error::{Result, VolveError};
+use crate::memory::MEMORY_SIZE;
+use std::ops::RangeInclusive;
+use std::path::Path;
+
+/// What happens to writes that hit a ROM region.
+#[derive(Copy, Debug, Clone, PartialEq, Eq, Default)]
+pub enum RomWritePolicy {
+    /// Drop the write silently, like the hardware does.
+    #[default]
+    Ignore,
+    /// Drop the write, but record it so it can be picked up with
+    /// `MemoryMap::take_rom_writes`.
+    Report,
+    /// Drop the write and report a bus fault, which stops the CPU.
+    Fault,
+}
+
+/// A write that was dropped because it hit ROM.
+#[derive(Copy, Debug, Clone, PartialEq, Eq)]
+pub struct RomWrite {
+    pub address: u16,
+    pub value: u8,
+}
+
+enum Kind {
+    Ram,
+    Rom,
+    /// Repeats the given range over the whole region.
+    Mirror(RangeInclusive<u16>),
+    /// Index of the device handling the region.
+    Device(usize),
+    Banked(Banks),
+    Unmapped,
+}
+
+/// Equally sized banks sharing a region, of which one is paged in at a time.
+struct Banks {
+    banks: Vec<Vec<u8>>,
+    /// Address of the latch that selects the active bank.
+    register: u16,
+    active: usize,
+    writable: bool,
+}
+
+struct Region {
+    range: RangeInclusive<u16>,
+    kind: Kind,
+}
+
+pub struct MemoryMap {
+    // backing store of the RAM and ROM regions, indexed by address
+    bytes: Vec<u8>,
+    regions: Vec<Region>,
+    devices: Devices,
+    open_bus: u8,
+    rom_write_policy: RomWritePolicy,
+    rom_writes: Vec<RomWrite>,
+    fault: Option<u16>,
+}
+
+/// Registers the regions of a `MemoryMap`. When regions overlap, the one
+/// registered last wins, so a device can be punched into a larger RAM area.
+/// Addresses outside of any region are unmapped.
+///
+/// Regions that cannot be mapped, like an empty range or a banked region
+/// without banks, are reported as `VolveError::InvalidRegion`.
+pub struct MemoryMapBuilder {
+    map: MemoryMap,
+}
+
+impl MemoryMap {
+    pub fn builder() -> MemoryMapBuilder {
+        MemoryMapBuilder {
+            map: MemoryMap {
+                bytes: vec![0; MEMORY_SIZE],
+                regions: Vec::new(),
+                devices: Devices::new(),
+                open_bus: 0xFF,
+                rom_write_policy: RomWritePolicy::default(),
+                rom_writes: Vec::new(),
+                fault: None,
+            },
+        }
+    }
+
+    /// Returns the ROM writes recorded under `RomWritePolicy::Report` since
+    /// the last call.
+    pub fn take_rom_writes(&mut self) -> Vec<RomWrite> {
+        std::mem::take(&mut self.rom_writes)
+    }
+
+    /// Pages in the banks selected by a write to their bank register and
+    /// returns whether `address` is one. The bank number wraps around at the
+    /// number of banks.
+    fn select_banks(&mut self, address: u16, value: u8) -> bool {
+        let mut selected = false;
+        for region in self.regions.iter_mut() {
+            if let Kind::Banked(banks) = &mut region.kind {
+                if banks.register == address {
+                    banks.active = value as usize % banks.banks.len();
+                    selected = true;
+                }
+            }
+        }
+        selected
+    }
+
+    fn find(&self, address: u16) -> Option<usize> {
+        self.regions
+            .iter()
+            .rposition(|region| region.range.contains(&address))
+    }
+
+    /// Follows mirrors down to the address they stand for. Mirrors that end
+    /// up pointing back at themselves leave the address unmapped.
+    fn unmirror(&self, mut address: u16) -> (u16, Option<usize>) {
+        // a chain without cycles visits every region at most once
+        for _ in 0..=self.regions.len() {
+            let index = match self.find(address) {
+                Some(index) => index,
+                None => return (address, None),
+            };
+            let region = &self.regions[index];
+
+            match &region.kind {
+                Kind::Mirror(target) => {
+                    let offset = (address - region.range.start()) as u32 % range_len(target);
+                    address = target.start() + offset as u16;
+                }
+                _ => return (address, Some(index)),
+            }
+        }
+        (address, None)
+    }
+}
+
+impl Bus for MemoryMap {
+    fn read(&mut self, address: u16) -> u8 {
+        let (address, index) = self.unmirror(address);
+        let region = match index {
+            Some(index) => &mut self.regions[index],
+            None => return self.open_bus,
+        };
+
+        let offset = address - region.range.start();
+        match &mut region.kind {
+            Kind::Ram | Kind::Rom => self.bytes[address as usize],
+            Kind::Device(index) => self.devices.read(*index, address),
+            Kind::Banked(banks) => banks.banks[banks.active][offset as usize],
+            Kind::Mirror(_) | Kind::Unmapped => self.open_bus,
+        }
+    }
+
+    fn write(&mut self, address: u16, value: u8) {
+        // bank latches are commonly decoded over ROM, where writing to them
+        // is what the program is meant to do
+        let selected = self.select_banks(address, value);
+
+        let (address, index) = self.unmirror(address);
+        let region = match index {
+            Some(index) => &mut self.regions[index],
+            None => return,
+        };
+
+        let offset = address - region.range.start();
+        match &mut region.kind {
+            Kind::Ram => self.bytes[address as usize] = value,
+            Kind::Banked(banks) if banks.writable => {
+                banks.banks[banks.active][offset as usize] = value;
+            }
+            Kind::Rom | Kind::Banked(_) if selected => {}
+            Kind::Rom | Kind::Banked(_) => match self.rom_write_policy {
+                RomWritePolicy::Ignore => {}
+                RomWritePolicy::Report => self.rom_writes.push(RomWrite { address, value }),
+                RomWritePolicy::Fault => self.fault = Some(address),
+            },
+            Kind::Device(index) => self.devices.write(*index, address, value),
+            Kind::Mirror(_) | Kind::Unmapped => {}
+        }
+    }
+
+    fn peek(&self, address: u16) -> u8 {
+        let (address, index) = self.unmirror(address);
+        let region = match index {
+            Some(index) => &self.regions[index],
+            None => return self.open_bus,
+        };
+
+        let offset = address - region.range.start();
+        match &region.kind {
+            Kind::Ram | Kind::Rom => self.bytes[address as usize],
+            Kind::Device(index) => self.devices.peek(*index, address),
+            Kind::Banked(banks) => banks.banks[banks.active][offset as usize],
+            Kind::Mirror(_) | Kind::Unmapped => self.open_bus,
+        }
+    }
+
+    fn poke(&mut self, address: u16, value: u8) {
+        let (address, index) = self.unmirror(address);
+        let region = match index {
+            Some(index) => &mut self.regions[index],
+            None => return,
+        };
+
+        let offset = address - region.range.start();
+        match &mut region.kind {
+            Kind::Ram | Kind::Rom => self.bytes[address as usize] = value,
+            Kind::Banked(banks) => banks.banks[banks.active][offset as usize] = value,
+            Kind::Device(_) | Kind::Mirror(_) | Kind::Unmapped => {}
+        }
+    }
+
+    fn can_poke(&self, address: u16) -> bool {
+        let (_, index) = self.unmirror(address);
+
+        index.is_some_and(|index| {
+            matches!(
+                self.regions[index].kind,
+                Kind::Ram | Kind::Rom | Kind::Banked(_)
+            )
+        })
+    }
+
+    fn bank(&self, address: u16) -> Option<usize> {
+        let (address, index) = self.unmirror(address);
+
+        match &self.regions[index?].kind {
+            Kind::Banked(banks) => Some(banks.active),
+            _ => None,
+        }
+    }
+
+    fn sync(&mut self, cycles: u64) {
+        self.devices.sync(cycles);
+    }
+
+    fn irq(&self) -> bool {
+        self.devices.irq()
+    }
+
+    fn take_nmi(&mut self) -> bool {
+        self.devices.take_nmi()
+    }
+
+    fn take_fault(&mut self) -> Option<u16> {
+        self.fault.take()
+    }
+
+    fn exit_code(&self) -> Option<u8> {
+        self.devices.exit_code()
+    }
+}
+
+impl MemoryMapBuilder {
+    pub fn ram(self, range: RangeInclusive<u16>) -> Result<MemoryMapBuilder> {
+        self.region(range, Kind::Ram)
+    }
+
+    /// Adds a ROM region holding `image` at its start.
+    pub fn rom(mut self, range: RangeInclusive<u16>, image: &[u8]) -> Result<MemoryMapBuilder> {
+        let start = *range.start() as usize;
+        code::check_fits(image, &range)?;
+
+        self.map.bytes[start..start + image.len()].copy_from_slice(image);
+        self.region(range, Kind::Rom)
+    }
+
+    /// Makes `range` repeat the contents of `target`, which is usually
+    /// smaller, such as 2K of RAM that is decoded over 8K.
+    pub fn mirror(
+        self,
+        range: RangeInclusive<u16>,
+        target: RangeInclusive<u16>,
+    ) -> Result<MemoryMapBuilder> {
+        if target.is_empty() {
+            return Err(invalid(&target, "empty memory region"));
+        }
+        if !(range.end() < target.start() || target.end() < range.start()) {
+            return Err(invalid(
+                &range,
+                "a mirror cannot overlap the range it mirrors",
+            ));
+        }
+        self.region(range, Kind::Mirror(target))
+    }
+
+    /// Hands all accesses to `range` over to `device`, with addresses
+    /// relative to the start of the range.
+    pub fn device<D: Device + 'static>(
+        mut self,
+        range: RangeInclusive<u16>,
+        device: D,
+    ) -> Result<MemoryMapBuilder> {
+        if range.is_empty() {
+            return Err(invalid(&range, "empty memory region"));
+        }
+        let index = self.map.devices.attach(range.clone(), device);
+        self.region(range, Kind::Device(index))
+    }
+
+    /// Adds `count` banks of RAM sharing `range`. Writing a bank number to
+    /// `register` pages that bank in, bank 0 is active at first.
+    pub fn banked_ram(
+        self,
+        range: RangeInclusive<u16>,
+        register: u16,
+        count: usize,
+    ) -> Result<MemoryMapBuilder> {
+        if count == 0 {
+            return Err(invalid(&range, "a banked region needs at least one bank"));
+        }
+        if range.is_empty() {
+            return Err(invalid(&range, "empty memory region"));
+        }
+        let banks = vec![vec![0; range_len(&range) as usize]; count];
+        self.banked(range, register, banks, true)
+    }
+
+    /// Adds a ROM bank for each of `images`, paged in through `register`
+    /// like with `banked_ram`.
+    pub fn banked_rom(
+        self,
+        range: RangeInclusive<u16>,
+        register: u16,
+        images: &[Vec<u8>],
+    ) -> Result<MemoryMapBuilder> {
+        if images.is_empty() {
+            return Err(invalid(&range, "a banked region needs at least one bank"));
+        }
+        if range.is_empty() {
+            return Err(invalid(&range, "empty memory region"));
+        }
+        let size = range_len(&range) as usize;
+        let banks = images
+            .iter()
+            .map(|image| {
+                code::check_fits(image, &range)?;
+                let mut bank = vec![0; size];
+                bank[..image.len()].copy_from_slice(image);
+                Ok(bank)
+            })
+            .collect::<Result<_>>()?;
+        self.banked(range, register, banks, false)
+    }
+
+    /// Like `banked_rom`, with one image file per bank.
+    pub fn banked_rom_files<P: AsRef<Path>>(
+        self,
+        range: RangeInclusive<u16>,
+        register: u16,
+        paths: &[P],
+    ) -> Result<MemoryMapBuilder> {
+        let images = paths
+            .iter()
+            .map(code::read_file)
+            .collect::<Result<Vec<_>>>()?;
+        self.banked_rom(range, register, &images)
+    }
+
+    /// Explicitly unmaps `range`, for instance to cut a hole into a larger
+    /// region.
+    pub fn unmapped(self, range: RangeInclusive<u16>) -> Result<MemoryMapBuilder> {
+        self.region(range, Kind::Unmapped)
+    }
+
+    /// Value read from unmapped addresses, $FF unless set.
+    pub fn open_bus(mut self, value: u8) -> MemoryMapBuilder {
+        self.map.open_bus = value;
+        self
+    }
+
+    pub fn rom_write_policy(mut self, policy: RomWritePolicy) -> MemoryMapBuilder {
+        self.map.rom_write_policy = policy;
+        self
+    }
+
+    pub fn build(self) -> MemoryMap {
+        self.map
+    }
+
+    fn banked(
+        self,
+        range: RangeInclusive<u16>,
+        register: u16,
+        banks: Vec<Vec<u8>>,
+        writable: bool,
+    ) -> Result<MemoryMapBuilder> {
+        let banks = Banks {
+            banks,
+            register,
+            active: 0,
+            writable,
+        };
+        self.region(range, Kind::Banked(banks))
+    }
+
+    fn region(mut self, range: RangeInclusive<u16>, kind: Kind) -> Result<MemoryMapBuilder> {
+        if range.is_empty() {
+            return Err(invalid(&range, "empty memory region"));
+        }
+        self.map.regions.push(Region { range, kind });
+        Ok(self)
+    }
+}
+
+fn invalid(range: &RangeInclusive<u16>, reason: &'static str) -> VolveError {
+    VolveError::InvalidRegion {
+        start: *range.start(),
+        end: *range.end(),
+        reason,
+    }
+}
+
+fn range_len(range: &RangeInclusive<u16>) -> u32 {
+    (*range.end() as u32) - (*range.start() as u32) + 1
+}
@@ -1,3 +1,7 @@
+use crate::bus::Bus;
+use crate::device::{Device, Devices};
+use std::ops::RangeInclusive;
+
 pub const MEMORY_SIZE: usize = 64 * 1024;
 
 pub const MEMORY_LOW_ADDRESS: u16 = 0x0000;
@@ -15,15 +19,30 @@ pub const IRQ: u16 = 0xFFFE;
 
 pub struct Memory {
     bytes: [u8; MEMORY_SIZE],
+    devices: Devices,
+}
+
+impl Default for Memory {
+    fn default() -> Memory {
+        Memory::new()
+    }
 }
 
 impl Memory {
     pub fn new() -> Memory {
         Memory {
             bytes: [0; MEMORY_SIZE],
+            devices: Devices::new(),
         }
     }
 
+    /// Maps `device` over `range`. Bus accesses to the range go to the
+    /// device, while `read_byte` and `write_byte` still reach the bytes
+    /// underneath.
+    pub fn attach<D: Device + 'static>(&mut self, range: RangeInclusive<u16>, device: D) {
+        self.devices.attach(range, device);
+    }
+
     pub fn read_byte(&self, address: u16) -> u8 {
         self.bytes[address as usize]
     }
@@ -34,13 +53,13 @@ impl Memory {
 
     pub fn read_word(&self, addr: u16) -> u16 {
         let lower = self.read_byte(addr) as u16;
-        let upper = self.read_byte(addr + 1) as u16;
+        let upper = self.read_byte(addr.wrapping_add(1)) as u16;
         upper << 8 | lower
     }
 
     pub fn write_word(&mut self, addr: u16, val: u16) {
         self.write_byte(addr, val as u8);
-        self.write_byte(addr + 1, (val >> 8) as u8);
+        self.write_byte(addr.wrapping_add(1), (val >> 8) as u8);
     }
 
     pub fn print_mem(&self) {
@@ -49,3 +68,52 @@ impl Memory {
         }
     }
 }
+
+impl Bus for Memory {
+    fn read(&mut self, address: u16) -> u8 {
+        match self.devices.find(address) {
+            Some(index) => self.devices.read(index, address),
+            None => self.read_byte(address),
+        }
+    }
+
+    fn write(&mut self, address: u16, value: u8) {
+        match self.devices.find(address) {
+            Some(index) => self.devices.write(index, address, value),
+            None => self.write_byte(address, value),
+        }
+    }
+
+    fn peek(&self, address: u16) -> u8 {
+        match self.devices.find(address) {
+            Some(index) => self.devices.peek(index, address),
+            None => self.read_byte(address),
+        }
+    }
+
+    fn poke(&mut self, address: u16, value: u8) {
+        if self.devices.find(address).is_none() {
+            self.write_byte(address, value);
+        }
+    }
+
+    fn can_poke(&self, address: u16) -> bool {
+        self.devices.find(address).is_none()
+    }
+
+    fn sync(&mut self, cycles: u64) {
+        self.devices.sync(cycles);
+    }
+
+    fn irq(&self) -> bool {
+        self.devices.irq()
+    }
+
+    fn take_nmi(&mut self) -> bool {
+        self.devices.take_nmi()
+    }
+
+    fn exit_code(&self) -> Option<u8> {
+        self.devices.exit_code()
+    }
+}
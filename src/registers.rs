@@ -19,6 +19,12 @@ pub struct Registers {
     pub pc: u16,
 }
 
+impl Default for Registers {
+    fn default() -> Registers {
+        Registers::new()
+    }
+}
+
 impl Registers {
     pub fn set_flag(&mut self, flag: StatusFlag, mode: bool) {
         if mode {
@@ -28,10 +34,15 @@ impl Registers {
         }
     }
 
-    pub fn get_flag(&mut self, flag: StatusFlag) -> bool {
+    pub fn get_flag(&self, flag: StatusFlag) -> bool {
         (self.p & flag as u8) != 0
     }
 
+    pub fn update_nz_flags(&mut self, value: u8) {
+        self.set_flag(StatusFlag::Zero, value == 0);
+        self.set_flag(StatusFlag::Negative, value & 0x80 != 0);
+    }
+
     pub fn new() -> Registers {
         Registers {
             a: 0,
@@ -0,0 +1,63 @@
+//! The members of the 6502 family Volve can emulate
+
+use crate::instruction::{AddressingMode, Instruction, OpCode, NMOS_OP_CODES, OP_CODES};
+
+/// Opcodes a CMOS part does not implement execute as single byte,
+/// single cycle NOPs.
+const UNIMPLEMENTED_NOP: Instruction = Instruction {
+    opcode: OpCode::NOP,
+    mode: AddressingMode::Implied,
+    length: 1,
+    cycles: 1,
+};
+
+#[derive(Copy, Debug, Clone, PartialEq, Eq, Default)]
+pub enum CpuVariant {
+    /// The original MOS 6502 and its second sources.
+    Nmos6502,
+    /// Rockwell R65C02: the W65C02 instruction set without WAI and STP.
+    Rockwell65C02,
+    /// Western Design Center W65C02S.
+    #[default]
+    Wdc65C02,
+    /// Synertek/GTE 65SC02: the Rockwell set without the bit instructions
+    /// RMB, SMB, BBR and BBS.
+    Synertek65SC02,
+    /// Ricoh 2A03/2A07 of the NES: an NMOS core with the BCD logic cut off.
+    Ricoh2A03,
+}
+
+impl CpuVariant {
+    /// Whether the part is one of the CMOS redesigns, which fixed the bugs
+    /// of the NMOS core at the cost of a few extra cycles.
+    pub fn is_cmos(self) -> bool {
+        !matches!(self, CpuVariant::Nmos6502 | CpuVariant::Ricoh2A03)
+    }
+
+    /// Whether ADC and SBC honour the decimal flag. The 2A03 still lets
+    /// programs set and clear it, it just has no effect.
+    pub fn has_decimal_mode(self) -> bool {
+        self != CpuVariant::Ricoh2A03
+    }
+
+    pub fn decode(self, bytecode: u8) -> Option<Instruction> {
+        let insn = match self {
+            CpuVariant::Nmos6502 | CpuVariant::Ricoh2A03 => NMOS_OP_CODES[bytecode as usize]?,
+            _ => OP_CODES[bytecode as usize]?,
+        };
+
+        let implemented = match self {
+            CpuVariant::Rockwell65C02 => !insn.opcode.is_wdc_extension(),
+            CpuVariant::Synertek65SC02 => {
+                !insn.opcode.is_wdc_extension() && !insn.opcode.is_bit_instruction()
+            }
+            _ => true,
+        };
+
+        if implemented {
+            Some(insn)
+        } else {
+            Some(UNIMPLEMENTED_NOP)
+        }
+    }
+}
@@ -0,0 +1,1274 @@
+use super::instruction::{AddressingMode, Instruction, OpCode, OP_CODES};
+use super::memory::{
+    Memory, ADDRESS_MASK, BRK_NATIVE, COP, COP_NATIVE, IRQ, IRQ_NATIVE, NMI, NMI_NATIVE, RESET,
+    STACK_LOW_ADDRESS,
+};
+use super::registers::{Registers, StatusFlag};
+use crate::cpu::State;
+use crate::error::{Result, VolveError};
+
+/// Operand of an instruction, as resolved from its addressing mode.
+#[derive(Copy, Debug, Clone)]
+pub struct Operand {
+    /// Effective 24-bit address, `None` for accumulator operands.
+    pub address: Option<u32>,
+    /// Operand value, only the low byte is valid for 8-bit accesses.
+    pub value: u16,
+    /// Whether indexing carried the address into another page.
+    pub page_crossed: bool,
+}
+
+pub struct Cpu {
+    pub registers: Registers,
+    pub memory: Memory,
+    cycles: u64,
+    // cycles the current instruction spends on top of its base count
+    penalty_cycles: u8,
+    irq: bool,
+    nmi_pending: bool,
+    state: State,
+}
+
+impl Default for Cpu {
+    fn default() -> Cpu {
+        Cpu::new()
+    }
+}
+
+impl Cpu {
+    pub fn new() -> Cpu {
+        Cpu {
+            registers: Registers::new(),
+            memory: Memory::new(),
+            cycles: 0,
+            penalty_cycles: 0,
+            irq: false,
+            nmi_pending: false,
+            state: State::Running,
+        }
+    }
+
+    /// Resets the CPU and runs it until a step fails, or STP stops it, which
+    /// ends the run with `VolveError::Halted`.
+    pub fn run(&mut self) -> Result<()> {
+        self.reset();
+
+        while self.state != State::Stopped {
+            self.step()?;
+        }
+        Err(VolveError::Halted)
+    }
+
+    pub fn state(&self) -> State {
+        self.state
+    }
+
+    /// Number of cycles the CPU has run for since it was created.
+    pub fn cycles(&self) -> u64 {
+        self.cycles
+    }
+
+    /// Runs the reset sequence. The 65C816 always comes out of reset in
+    /// emulation mode, with D, DBR and PBR cleared and the stack in page $01.
+    pub fn reset(&mut self) {
+        self.registers.d = 0;
+        self.registers.dbr = 0;
+        self.registers.pbr = 0;
+        self.registers.set_emulation(true);
+        self.registers.set_flag(StatusFlag::NoInterrupts, true);
+        self.registers.set_flag(StatusFlag::Decimal, false);
+        self.registers.pc = self.memory.read_word(RESET as u32);
+        self.nmi_pending = false;
+        self.state = State::Running;
+        self.cycles += 7;
+    }
+
+    /// Pulls the IRQ line low. The request stays active until it is released
+    /// and is only serviced while the I flag is clear.
+    pub fn assert_irq(&mut self) {
+        self.irq = true;
+    }
+
+    pub fn release_irq(&mut self) {
+        self.irq = false;
+    }
+
+    /// Signals a falling edge on the NMI line. The interrupt is serviced once
+    /// before the next instruction, regardless of the I flag.
+    pub fn trigger_nmi(&mut self) {
+        self.nmi_pending = true;
+    }
+
+    /// Pushes the return address and P, masks interrupts and continues at the
+    /// address stored in `vector` of bank 0. Native mode also pushes PBR,
+    /// which costs one more cycle.
+    fn interrupt(&mut self, return_addr: u16, vector: u16, brk: bool) {
+        if !self.registers.emulation {
+            self.push_byte(self.registers.pbr);
+            self.penalty_cycles += 1;
+        }
+        self.push_word(return_addr);
+        self.push_status(brk);
+        self.registers.set_flag(StatusFlag::NoInterrupts, true);
+        self.registers.set_flag(StatusFlag::Decimal, false);
+        self.registers.pbr = 0;
+        self.registers.pc = self.memory.read_word(vector as u32);
+    }
+
+    fn service_interrupt(&mut self, native: u16, emulation: u16) -> u8 {
+        let vector = if self.registers.emulation {
+            emulation
+        } else {
+            native
+        };
+        self.penalty_cycles = 0;
+        self.interrupt(self.registers.pc, vector, false);
+
+        let cycles = 7 + self.penalty_cycles;
+        self.cycles += cycles as u64;
+        cycles
+    }
+
+    /// Services a pending interrupt or executes a single instruction and
+    /// returns the number of cycles it took. While the CPU is halted, a step
+    /// idles for a single cycle instead.
+    pub fn step(&mut self) -> Result<u8> {
+        match self.state {
+            State::Stopped => return Ok(self.idle()),
+            State::Waiting if self.nmi_pending || self.irq => self.state = State::Running,
+            State::Waiting => return Ok(self.idle()),
+            State::Running => {}
+        }
+
+        if self.nmi_pending {
+            self.nmi_pending = false;
+            return Ok(self.service_interrupt(NMI_NATIVE, NMI));
+        }
+        if self.irq && !self.registers.get_flag(StatusFlag::NoInterrupts) {
+            return Ok(self.service_interrupt(IRQ_NATIVE, IRQ));
+        }
+
+        let bytecode = self.fetch_insn();
+        let insn = self.decode_bytecode(bytecode)?;
+        // taken before executing, REP and SEP may change the register widths
+        let length = insn.length as u16 + self.immediate_extension(insn);
+        self.penalty_cycles = 0;
+        self.execute_insn(insn);
+
+        // the program counter wraps around within the program bank
+        if !insn.opcode.transfers_control() {
+            self.registers.pc = self.registers.pc.wrapping_add(length);
+        }
+
+        let cycles = insn.cycles + self.penalty_cycles;
+        self.cycles += cycles as u64;
+        Ok(cycles)
+    }
+
+    fn idle(&mut self) -> u8 {
+        self.cycles += 1;
+        1
+    }
+
+    fn fetch_insn(&mut self) -> u8 {
+        self.memory.read_byte(self.program_address(0))
+    }
+
+    fn decode_bytecode(&self, bytecode: u8) -> Result<Instruction> {
+        OP_CODES[bytecode as usize].ok_or(VolveError::InvalidOpcode {
+            opcode: bytecode,
+            address: self.program_address(0),
+        })
+    }
+
+    /// Immediate operands of 16-bit registers take an extra byte. REP and
+    /// SEP always take a single byte.
+    fn immediate_extension(&self, insn: Instruction) -> u16 {
+        if insn.mode != AddressingMode::Immediate {
+            return 0;
+        }
+        let wide = match insn.opcode {
+            OpCode::REP | OpCode::SEP => false,
+            opcode => self.is_wide(opcode),
+        };
+        wide as u16
+    }
+
+    /// Whether `opcode` works on 16-bit data with the current M and X flags.
+    fn is_wide(&self, opcode: OpCode) -> bool {
+        if opcode.uses_index_registers() {
+            self.registers.is_index_wide()
+        } else {
+            self.registers.is_accumulator_wide()
+        }
+    }
+
+    /// Address `offset` bytes past pc, within the program bank.
+    fn program_address(&self, offset: u16) -> u32 {
+        (self.registers.pbr as u32) << 16 | self.registers.pc.wrapping_add(offset) as u32
+    }
+
+    fn read_program_byte(&self, offset: u16) -> u8 {
+        self.memory.read_byte(self.program_address(offset))
+    }
+
+    fn read_program_word(&self, offset: u16) -> u16 {
+        let lower = self.read_program_byte(offset) as u16;
+        let upper = self.read_program_byte(offset + 1) as u16;
+        upper << 8 | lower
+    }
+
+    /// Pushes a byte onto the stack in bank 0. In emulation mode the stack
+    /// pointer wraps around within page $01.
+    pub fn push_byte(&mut self, value: u8) {
+        self.memory.write_byte(self.registers.sp as u32, value);
+        self.registers.sp = self.registers.sp.wrapping_sub(1);
+        if self.registers.emulation {
+            self.registers.sp = STACK_LOW_ADDRESS | (self.registers.sp & 0x00FF);
+        }
+    }
+
+    pub fn pull_byte(&mut self) -> u8 {
+        self.registers.sp = self.registers.sp.wrapping_add(1);
+        if self.registers.emulation {
+            self.registers.sp = STACK_LOW_ADDRESS | (self.registers.sp & 0x00FF);
+        }
+        self.memory.read_byte(self.registers.sp as u32)
+    }
+
+    /// Pushes a word high byte first, so it ends up little endian in memory.
+    pub fn push_word(&mut self, value: u16) {
+        self.push_byte((value >> 8) as u8);
+        self.push_byte(value as u8);
+    }
+
+    pub fn pull_word(&mut self) -> u16 {
+        let lower = self.pull_byte() as u16;
+        let upper = self.pull_byte() as u16;
+        upper << 8 | lower
+    }
+
+    /// Pushes a byte or a word, depending on `wide`.
+    fn push_value(&mut self, value: u16, wide: bool) {
+        if wide {
+            self.push_word(value);
+        } else {
+            self.push_byte(value as u8);
+        }
+    }
+
+    fn pull_value(&mut self, wide: bool) -> u16 {
+        if wide {
+            self.pull_word()
+        } else {
+            self.pull_byte() as u16
+        }
+    }
+
+    /// Pushes P. In emulation mode bit 4 is the B flag of the 6502, which is
+    /// set for BRK and PHP, but clear for IRQ and NMI.
+    fn push_status(&mut self, brk: bool) {
+        let mut p = self.registers.p;
+
+        if self.registers.emulation {
+            let b = StatusFlag::IndexRegisters as u8;
+            p = if brk { p | b } else { p & !b };
+        }
+        self.push_byte(p);
+    }
+
+    fn pull_status(&mut self) {
+        let value = self.pull_byte();
+        self.registers.set_status(value);
+    }
+
+    /// Takes the branch when `condition` holds and falls through to the next
+    /// instruction otherwise. The displacement counts from the address of the
+    /// next instruction.
+    ///
+    /// A taken branch costs one extra cycle. Only in emulation mode does
+    /// crossing into another page cost one more.
+    fn branch(&mut self, mode: AddressingMode, condition: bool) {
+        let (length, displacement) = match mode {
+            AddressingMode::Relative => (2, self.read_program_byte(1) as i8 as u16),
+            AddressingMode::RelativeLong => (3, self.read_program_word(1)),
+            _ => unreachable!("Invalid addressing mode {:?} for a branch", mode),
+        };
+        let next = self.registers.pc.wrapping_add(length);
+
+        if condition {
+            let target = next.wrapping_add(displacement);
+
+            if mode == AddressingMode::Relative {
+                self.penalty_cycles += 1;
+                if self.registers.emulation && (target & 0xFF00) != (next & 0xFF00) {
+                    self.penalty_cycles += 1;
+                }
+            }
+            self.registers.pc = target;
+        } else {
+            self.registers.pc = next;
+        }
+    }
+
+    pub fn execute_insn(&mut self, insn: Instruction) {
+        let wide = self.is_wide(insn.opcode);
+
+        match insn.opcode {
+            OpCode::ADC => self.adc(insn.mode, wide),
+            OpCode::AND => self.and(insn.mode, wide),
+            OpCode::ASL => self.asl(insn.mode, wide),
+            OpCode::BCC => self.bcc(insn.mode),
+            OpCode::BCS => self.bcs(insn.mode),
+            OpCode::BEQ => self.beq(insn.mode),
+            OpCode::BIT => self.bit(insn.mode, wide),
+            OpCode::BMI => self.bmi(insn.mode),
+            OpCode::BNE => self.bne(insn.mode),
+            OpCode::BPL => self.bpl(insn.mode),
+            OpCode::BRA => self.bra(insn.mode),
+            OpCode::BRK => self.brk(insn.mode),
+            OpCode::BRL => self.brl(insn.mode),
+            OpCode::BVC => self.bvc(insn.mode),
+            OpCode::BVS => self.bvs(insn.mode),
+            OpCode::CLC => self.clc(insn.mode),
+            OpCode::CLD => self.cld(insn.mode),
+            OpCode::CLI => self.cli(insn.mode),
+            OpCode::CLV => self.clv(insn.mode),
+            OpCode::CMP => self.cmp(insn.mode, wide),
+            OpCode::COP => self.cop(insn.mode),
+            OpCode::CPX => self.cpx(insn.mode, wide),
+            OpCode::CPY => self.cpy(insn.mode, wide),
+            OpCode::DEC => self.dec(insn.mode, wide),
+            OpCode::DEX => self.dex(insn.mode),
+            OpCode::DEY => self.dey(insn.mode),
+            OpCode::EOR => self.eor(insn.mode, wide),
+            OpCode::INC => self.inc(insn.mode, wide),
+            OpCode::INX => self.inx(insn.mode),
+            OpCode::INY => self.iny(insn.mode),
+            OpCode::JML => self.jml(insn.mode),
+            OpCode::JMP => self.jmp(insn.mode),
+            OpCode::JSL => self.jsl(insn.mode),
+            OpCode::JSR => self.jsr(insn.mode),
+            OpCode::LDA => self.lda(insn.mode, wide),
+            OpCode::LDX => self.ldx(insn.mode, wide),
+            OpCode::LDY => self.ldy(insn.mode, wide),
+            OpCode::LSR => self.lsr(insn.mode, wide),
+            OpCode::MVN => self.mvn(insn.mode),
+            OpCode::MVP => self.mvp(insn.mode),
+            OpCode::NOP => self.nop(insn.mode),
+            OpCode::ORA => self.ora(insn.mode, wide),
+            OpCode::PEA => self.pea(insn.mode),
+            OpCode::PEI => self.pei(insn.mode),
+            OpCode::PER => self.per(insn.mode),
+            OpCode::PHA => self.pha(insn.mode, wide),
+            OpCode::PHB => self.phb(insn.mode),
+            OpCode::PHD => self.phd(insn.mode),
+            OpCode::PHK => self.phk(insn.mode),
+            OpCode::PHP => self.php(insn.mode),
+            OpCode::PHX => self.phx(insn.mode, wide),
+            OpCode::PHY => self.phy(insn.mode, wide),
+            OpCode::PLA => self.pla(insn.mode, wide),
+            OpCode::PLB => self.plb(insn.mode),
+            OpCode::PLD => self.pld(insn.mode),
+            OpCode::PLP => self.plp(insn.mode),
+            OpCode::PLX => self.plx(insn.mode, wide),
+            OpCode::PLY => self.ply(insn.mode, wide),
+            OpCode::REP => self.rep(insn.mode),
+            OpCode::ROL => self.rol(insn.mode, wide),
+            OpCode::ROR => self.ror(insn.mode, wide),
+            OpCode::RTI => self.rti(insn.mode),
+            OpCode::RTL => self.rtl(insn.mode),
+            OpCode::RTS => self.rts(insn.mode),
+            OpCode::SBC => self.sbc(insn.mode, wide),
+            OpCode::SEC => self.sec(insn.mode),
+            OpCode::SED => self.sed(insn.mode),
+            OpCode::SEI => self.sei(insn.mode),
+            OpCode::SEP => self.sep(insn.mode),
+            OpCode::STA => self.sta(insn.mode, wide),
+            OpCode::STP => self.stp(insn.mode),
+            OpCode::STX => self.stx(insn.mode, wide),
+            OpCode::STY => self.sty(insn.mode, wide),
+            OpCode::STZ => self.stz(insn.mode, wide),
+            OpCode::TAX => self.tax(insn.mode),
+            OpCode::TAY => self.tay(insn.mode),
+            OpCode::TCD => self.tcd(insn.mode),
+            OpCode::TCS => self.tcs(insn.mode),
+            OpCode::TDC => self.tdc(insn.mode),
+            OpCode::TRB => self.trb(insn.mode, wide),
+            OpCode::TSB => self.tsb(insn.mode, wide),
+            OpCode::TSC => self.tsc(insn.mode),
+            OpCode::TSX => self.tsx(insn.mode),
+            OpCode::TXA => self.txa(insn.mode),
+            OpCode::TXS => self.txs(insn.mode),
+            OpCode::TXY => self.txy(insn.mode),
+            OpCode::TYA => self.tya(insn.mode),
+            OpCode::TYX => self.tyx(insn.mode),
+            OpCode::WAI => self.wai(insn.mode),
+            OpCode::WDM => self.wdm(insn.mode),
+            OpCode::XBA => self.xba(insn.mode),
+            OpCode::XCE => self.xce(insn.mode),
+        }
+    }
+
+    /// Computes the 24-bit effective address of the instruction at pc. Data
+    /// accesses through 16-bit addresses go to the data bank, while direct
+    /// page and stack relative accesses always go to bank 0.
+    ///
+    /// Also returns whether indexing crossed a page boundary.
+    fn effective_address(&self, mode: AddressingMode) -> (u32, bool) {
+        let data_bank = (self.registers.dbr as u32) << 16;
+        let (x, y) = (self.registers.x, self.registers.y);
+
+        match mode {
+            AddressingMode::Immediate => (self.program_address(1), false),
+            AddressingMode::Direct => (self.direct_address(0), false),
+            AddressingMode::DirectX => (self.direct_address(x), false),
+            AddressingMode::DirectY => (self.direct_address(y), false),
+            AddressingMode::DirectIndirect => {
+                let pointer = self.direct_address(0);
+                (data_bank | self.read_bank_zero_word(pointer) as u32, false)
+            }
+            AddressingMode::DirectIndirectLong => {
+                let pointer = self.direct_address(0);
+                (self.memory.read_long(pointer), false)
+            }
+            AddressingMode::DirectIndexedIndirectX => {
+                let pointer = self.direct_address(x);
+                (data_bank | self.read_bank_zero_word(pointer) as u32, false)
+            }
+            AddressingMode::DirectIndirectIndexedY => {
+                let pointer = self.direct_address(0);
+                Cpu::index(data_bank | self.read_bank_zero_word(pointer) as u32, y)
+            }
+            AddressingMode::DirectIndirectLongIndexedY => {
+                let pointer = self.direct_address(0);
+                Cpu::index(self.memory.read_long(pointer), y)
+            }
+            AddressingMode::StackRelative => {
+                let offset = self.read_program_byte(1) as u16;
+                (self.registers.sp.wrapping_add(offset) as u32, false)
+            }
+            AddressingMode::StackRelativeIndirectIndexedY => {
+                let offset = self.read_program_byte(1) as u16;
+                let pointer = self.registers.sp.wrapping_add(offset) as u32;
+                Cpu::index(data_bank | self.read_bank_zero_word(pointer) as u32, y)
+            }
+            AddressingMode::Absolute => (data_bank | self.read_program_word(1) as u32, false),
+            AddressingMode::AbsoluteX => {
+                Cpu::index(data_bank | self.read_program_word(1) as u32, x)
+            }
+            AddressingMode::AbsoluteY => {
+                Cpu::index(data_bank | self.read_program_word(1) as u32, y)
+            }
+            AddressingMode::AbsoluteLong => (self.memory.read_long(self.program_address(1)), false),
+            AddressingMode::AbsoluteLongX => {
+                Cpu::index(self.memory.read_long(self.program_address(1)), x)
+            }
+            _ => unreachable!("Addressing mode {:?} has no effective address", mode),
+        }
+    }
+
+    /// Address in bank 0 of the direct page operand plus `index`. In
+    /// emulation mode with a page aligned D, indexing wraps around within the
+    /// direct page like the 6502 zero page.
+    fn direct_address(&self, index: u16) -> u32 {
+        let d = self.registers.d;
+        let offset = self.read_program_byte(1);
+
+        if self.registers.emulation && d & 0x00FF == 0 {
+            (d | offset.wrapping_add(index as u8) as u16) as u32
+        } else {
+            d.wrapping_add(offset as u16).wrapping_add(index) as u32
+        }
+    }
+
+    fn read_bank_zero_word(&self, pointer: u32) -> u16 {
+        let lower = self.memory.read_byte(pointer) as u16;
+        let upper = self
+            .memory
+            .read_byte((pointer as u16).wrapping_add(1) as u32) as u16;
+        upper << 8 | lower
+    }
+
+    fn index(base: u32, index: u16) -> (u32, bool) {
+        let address = base.wrapping_add(index as u32) & ADDRESS_MASK;
+        (address, (base & 0xFFFF00) != (address & 0xFFFF00))
+    }
+
+    fn read_value(&self, address: u32, wide: bool) -> u16 {
+        if wide {
+            self.memory.read_word(address)
+        } else {
+            self.memory.read_byte(address) as u16
+        }
+    }
+
+    fn write_value(&mut self, address: u32, value: u16, wide: bool) {
+        if wide {
+            self.memory.write_word(address, value);
+        } else {
+            self.memory.write_byte(address, value as u8);
+        }
+    }
+
+    /// Resolves the operand of the instruction at pc. A 16-bit access costs
+    /// one extra cycle for its second byte, a direct page that is not page
+    /// aligned another one.
+    fn resolve(&mut self, mode: AddressingMode, wide: bool) -> Operand {
+        if mode == AddressingMode::Accumulator {
+            return Operand {
+                address: None,
+                value: self.registers.accumulator(),
+                page_crossed: false,
+            };
+        }
+
+        let (address, page_crossed) = self.effective_address(mode);
+        if mode.uses_direct_page() && self.registers.d & 0x00FF != 0 {
+            self.penalty_cycles += 1;
+        }
+        if wide {
+            self.penalty_cycles += 1;
+        }
+        Operand {
+            address: Some(address),
+            value: self.read_value(address, wide),
+            page_crossed,
+        }
+    }
+
+    /// Reads the operand of a load, logic or arithmetic instruction. Indexed
+    /// reads cost one extra cycle when crossing a page, or always with 16-bit
+    /// index registers.
+    fn read_operand(&mut self, mode: AddressingMode, wide: bool) -> u16 {
+        let operand = self.resolve(mode, wide);
+
+        let indexed = matches!(
+            mode,
+            AddressingMode::AbsoluteX
+                | AddressingMode::AbsoluteY
+                | AddressingMode::DirectIndirectIndexedY
+        );
+        if indexed && (operand.page_crossed || self.registers.is_index_wide()) {
+            self.penalty_cycles += 1;
+        }
+        operand.value
+    }
+
+    fn write_operand(&mut self, mode: AddressingMode, value: u16, wide: bool) {
+        let (address, _) = self.effective_address(mode);
+        if mode.uses_direct_page() && self.registers.d & 0x00FF != 0 {
+            self.penalty_cycles += 1;
+        }
+        if wide {
+            self.penalty_cycles += 1;
+        }
+        self.write_value(address, value, wide);
+    }
+
+    /// Applies a read-modify-write operation to the accumulator or to memory.
+    /// 16-bit memory operands cost two extra cycles, one for each access.
+    fn modify<F>(&mut self, mode: AddressingMode, wide: bool, op: F)
+    where
+        F: FnOnce(&mut Self, u16) -> u16,
+    {
+        let operand = self.resolve(mode, wide);
+        let result = op(self, operand.value);
+
+        match operand.address {
+            Some(address) => {
+                if wide {
+                    self.penalty_cycles += 1;
+                }
+                self.write_value(address, result, wide);
+            }
+            None => self.registers.set_accumulator(result),
+        }
+    }
+
+    fn add(&mut self, operand: u16, wide: bool) {
+        let (mask, sign, digits) = if wide {
+            (0xFFFF, 0x8000, 4)
+        } else {
+            (0x00FF, 0x0080, 2)
+        };
+        let a = self.registers.accumulator() as u32;
+        let b = operand as u32 & mask;
+        let mut carry = self.registers.get_flag(StatusFlag::Carry) as u32;
+
+        let result = if self.registers.get_flag(StatusFlag::Decimal) {
+            // digit by digit, with a decimal carry between digits
+            let mut sum = 0;
+            for digit in 0..digits {
+                let shift = digit * 4;
+                let mut value = (a >> shift & 0xF) + (b >> shift & 0xF) + carry;
+                carry = (value > 9) as u32;
+                if carry == 1 {
+                    value += 6;
+                }
+                sum |= (value & 0xF) << shift;
+            }
+            sum | carry << (digits * 4)
+        } else {
+            a + b + carry
+        };
+
+        self.registers.set_flag(StatusFlag::Carry, result > mask);
+        self.registers
+            .set_flag(StatusFlag::Overflow, (!(a ^ b) & (a ^ result) & sign) != 0);
+        self.registers.set_accumulator(result as u16);
+        self.registers.update_nz_flags(result as u16, wide);
+    }
+
+    fn subtract(&mut self, operand: u16, wide: bool) {
+        if !self.registers.get_flag(StatusFlag::Decimal) {
+            self.add(!operand, wide);
+            return;
+        }
+
+        let (mask, sign, digits) = if wide {
+            (0xFFFF, 0x8000, 4)
+        } else {
+            (0x00FF, 0x0080, 2)
+        };
+        let a = self.registers.accumulator() as i32;
+        let b = operand as i32 & mask;
+        let mut borrow = !self.registers.get_flag(StatusFlag::Carry) as i32;
+
+        let mut difference = 0;
+        for digit in 0..digits {
+            let shift = digit * 4;
+            let mut value = (a >> shift & 0xF) - (b >> shift & 0xF) - borrow;
+            borrow = (value < 0) as i32;
+            if borrow == 1 {
+                value += 10;
+            }
+            difference |= (value & 0xF) << shift;
+        }
+
+        // V is the same as for the binary subtraction
+        let binary = a - b - !self.registers.get_flag(StatusFlag::Carry) as i32;
+        self.registers.set_flag(StatusFlag::Carry, borrow == 0);
+        self.registers
+            .set_flag(StatusFlag::Overflow, ((a ^ b) & (a ^ binary) & sign) != 0);
+        self.registers.set_accumulator(difference as u16);
+        self.registers.update_nz_flags(difference as u16, wide);
+    }
+
+    fn compare(&mut self, register: u16, operand: u16, wide: bool) {
+        let (register, operand) = if wide {
+            (register, operand)
+        } else {
+            (register & 0x00FF, operand & 0x00FF)
+        };
+        self.registers
+            .set_flag(StatusFlag::Carry, register >= operand);
+        self.registers
+            .update_nz_flags(register.wrapping_sub(operand), wide);
+    }
+
+    /// Sign bit of a value of the given width.
+    fn sign(wide: bool) -> u16 {
+        if wide {
+            0x8000
+        } else {
+            0x0080
+        }
+    }
+
+    fn set_x(&mut self, value: u16) {
+        let wide = self.registers.is_index_wide();
+        self.registers.x = if wide { value } else { value & 0x00FF };
+        self.registers.update_nz_flags(self.registers.x, wide);
+    }
+
+    fn set_y(&mut self, value: u16) {
+        let wide = self.registers.is_index_wide();
+        self.registers.y = if wide { value } else { value & 0x00FF };
+        self.registers.update_nz_flags(self.registers.y, wide);
+    }
+
+    fn adc(&mut self, mode: AddressingMode, wide: bool) {
+        let operand = self.read_operand(mode, wide);
+        self.add(operand, wide);
+    }
+
+    fn and(&mut self, mode: AddressingMode, wide: bool) {
+        let value = self.registers.accumulator() & self.read_operand(mode, wide);
+        self.registers.set_accumulator(value);
+        self.registers.update_nz_flags(value, wide);
+    }
+
+    fn asl(&mut self, mode: AddressingMode, wide: bool) {
+        self.modify(mode, wide, |cpu, value| {
+            let result = value << 1;
+            cpu.registers
+                .set_flag(StatusFlag::Carry, value & Cpu::sign(wide) != 0);
+            cpu.registers.update_nz_flags(result, wide);
+            result
+        });
+    }
+
+    fn bcc(&mut self, mode: AddressingMode) {
+        let condition = !self.registers.get_flag(StatusFlag::Carry);
+        self.branch(mode, condition);
+    }
+
+    fn bcs(&mut self, mode: AddressingMode) {
+        let condition = self.registers.get_flag(StatusFlag::Carry);
+        self.branch(mode, condition);
+    }
+
+    fn beq(&mut self, mode: AddressingMode) {
+        let condition = self.registers.get_flag(StatusFlag::Zero);
+        self.branch(mode, condition);
+    }
+
+    /// BIT #imm only affects Z, the other modes also copy the two topmost
+    /// bits of the operand into N and V.
+    fn bit(&mut self, mode: AddressingMode, wide: bool) {
+        let operand = self.read_operand(mode, wide);
+        let sign = Cpu::sign(wide);
+
+        self.registers.set_flag(
+            StatusFlag::Zero,
+            self.registers.accumulator() & operand == 0,
+        );
+        if mode != AddressingMode::Immediate {
+            self.registers
+                .set_flag(StatusFlag::Negative, operand & sign != 0);
+            self.registers
+                .set_flag(StatusFlag::Overflow, operand & (sign >> 1) != 0);
+        }
+    }
+
+    fn bmi(&mut self, mode: AddressingMode) {
+        let condition = self.registers.get_flag(StatusFlag::Negative);
+        self.branch(mode, condition);
+    }
+
+    fn bne(&mut self, mode: AddressingMode) {
+        let condition = !self.registers.get_flag(StatusFlag::Zero);
+        self.branch(mode, condition);
+    }
+
+    fn bpl(&mut self, mode: AddressingMode) {
+        let condition = !self.registers.get_flag(StatusFlag::Negative);
+        self.branch(mode, condition);
+    }
+
+    fn bra(&mut self, mode: AddressingMode) {
+        self.branch(mode, true);
+        // the base cycle count of BRA already covers the taken branch
+        self.penalty_cycles -= 1;
+    }
+
+    /// BRK and COP skip their signature byte. In emulation mode BRK goes
+    /// through the IRQ vector, with B set in the pushed copy of P.
+    fn brk(&mut self, mode: AddressingMode) {
+        let vector = if self.registers.emulation {
+            IRQ
+        } else {
+            BRK_NATIVE
+        };
+        self.interrupt(self.registers.pc.wrapping_add(2), vector, true);
+    }
+
+    fn brl(&mut self, mode: AddressingMode) {
+        self.branch(mode, true);
+    }
+
+    fn bvc(&mut self, mode: AddressingMode) {
+        let condition = !self.registers.get_flag(StatusFlag::Overflow);
+        self.branch(mode, condition);
+    }
+
+    fn bvs(&mut self, mode: AddressingMode) {
+        let condition = self.registers.get_flag(StatusFlag::Overflow);
+        self.branch(mode, condition);
+    }
+
+    fn clc(&mut self, mode: AddressingMode) {
+        self.registers.set_flag(StatusFlag::Carry, false);
+    }
+
+    fn cld(&mut self, mode: AddressingMode) {
+        self.registers.set_flag(StatusFlag::Decimal, false);
+    }
+
+    fn cli(&mut self, mode: AddressingMode) {
+        self.registers.set_flag(StatusFlag::NoInterrupts, false);
+    }
+
+    fn clv(&mut self, mode: AddressingMode) {
+        self.registers.set_flag(StatusFlag::Overflow, false);
+    }
+
+    fn cmp(&mut self, mode: AddressingMode, wide: bool) {
+        let operand = self.read_operand(mode, wide);
+        self.compare(self.registers.accumulator(), operand, wide);
+    }
+
+    fn cop(&mut self, mode: AddressingMode) {
+        let vector = if self.registers.emulation {
+            COP
+        } else {
+            COP_NATIVE
+        };
+        self.interrupt(self.registers.pc.wrapping_add(2), vector, false);
+    }
+
+    fn cpx(&mut self, mode: AddressingMode, wide: bool) {
+        let operand = self.read_operand(mode, wide);
+        self.compare(self.registers.x, operand, wide);
+    }
+
+    fn cpy(&mut self, mode: AddressingMode, wide: bool) {
+        let operand = self.read_operand(mode, wide);
+        self.compare(self.registers.y, operand, wide);
+    }
+
+    fn dec(&mut self, mode: AddressingMode, wide: bool) {
+        self.modify(mode, wide, |cpu, value| {
+            let result = value.wrapping_sub(1);
+            cpu.registers.update_nz_flags(result, wide);
+            result
+        });
+    }
+
+    fn dex(&mut self, mode: AddressingMode) {
+        self.set_x(self.registers.x.wrapping_sub(1));
+    }
+
+    fn dey(&mut self, mode: AddressingMode) {
+        self.set_y(self.registers.y.wrapping_sub(1));
+    }
+
+    fn eor(&mut self, mode: AddressingMode, wide: bool) {
+        let value = self.registers.accumulator() ^ self.read_operand(mode, wide);
+        self.registers.set_accumulator(value);
+        self.registers.update_nz_flags(value, wide);
+    }
+
+    fn inc(&mut self, mode: AddressingMode, wide: bool) {
+        self.modify(mode, wide, |cpu, value| {
+            let result = value.wrapping_add(1);
+            cpu.registers.update_nz_flags(result, wide);
+            result
+        });
+    }
+
+    fn inx(&mut self, mode: AddressingMode) {
+        self.set_x(self.registers.x.wrapping_add(1));
+    }
+
+    fn iny(&mut self, mode: AddressingMode) {
+        self.set_y(self.registers.y.wrapping_add(1));
+    }
+
+    /// JML al and JML [a] load both the program bank and pc.
+    fn jml(&mut self, mode: AddressingMode) {
+        let target = match mode {
+            AddressingMode::AbsoluteLong => self.memory.read_long(self.program_address(1)),
+            AddressingMode::AbsoluteIndirectLong => {
+                let pointer = self.read_program_word(1) as u32;
+                self.memory.read_long(pointer)
+            }
+            _ => unreachable!("Invalid addressing mode {:?} for JML", mode),
+        };
+        self.registers.pbr = (target >> 16) as u8;
+        self.registers.pc = target as u16;
+    }
+
+    /// JMP stays within the program bank. The pointer of JMP (a) is read from
+    /// bank 0, the one of JMP (a,x) from the program bank.
+    fn jmp(&mut self, mode: AddressingMode) {
+        self.registers.pc = match mode {
+            AddressingMode::Absolute => self.read_program_word(1),
+            AddressingMode::AbsoluteIndirect => {
+                let pointer = self.read_program_word(1) as u32;
+                self.memory.read_word(pointer)
+            }
+            AddressingMode::AbsoluteIndexedIndirect => self.read_indexed_pointer(),
+            _ => unreachable!("Invalid addressing mode {:?} for JMP", mode),
+        };
+    }
+
+    /// Reads the pointer of the (a,x) mode, which lives in the program bank.
+    fn read_indexed_pointer(&self) -> u16 {
+        let pointer = self.read_program_word(1).wrapping_add(self.registers.x);
+        let bank = (self.registers.pbr as u32) << 16;
+        let lower = self.memory.read_byte(bank | pointer as u32) as u16;
+        let upper = self.memory.read_byte(bank | pointer.wrapping_add(1) as u32) as u16;
+        upper << 8 | lower
+    }
+
+    /// JSL pushes the program bank before the address of its last byte.
+    fn jsl(&mut self, mode: AddressingMode) {
+        let target = self.memory.read_long(self.program_address(1));
+        self.push_byte(self.registers.pbr);
+        self.push_word(self.registers.pc.wrapping_add(3));
+        self.registers.pbr = (target >> 16) as u8;
+        self.registers.pc = target as u16;
+    }
+
+    /// JSR pushes the address of its last byte, RTS adds the missing one.
+    fn jsr(&mut self, mode: AddressingMode) {
+        let target = match mode {
+            AddressingMode::Absolute => self.read_program_word(1),
+            AddressingMode::AbsoluteIndexedIndirect => self.read_indexed_pointer(),
+            _ => unreachable!("Invalid addressing mode {:?} for JSR", mode),
+        };
+        self.push_word(self.registers.pc.wrapping_add(2));
+        self.registers.pc = target;
+    }
+
+    fn lda(&mut self, mode: AddressingMode, wide: bool) {
+        let value = self.read_operand(mode, wide);
+        self.registers.set_accumulator(value);
+        self.registers.update_nz_flags(value, wide);
+    }
+
+    fn ldx(&mut self, mode: AddressingMode, wide: bool) {
+        let value = self.read_operand(mode, wide);
+        self.set_x(value);
+    }
+
+    fn ldy(&mut self, mode: AddressingMode, wide: bool) {
+        let value = self.read_operand(mode, wide);
+        self.set_y(value);
+    }
+
+    fn lsr(&mut self, mode: AddressingMode, wide: bool) {
+        self.modify(mode, wide, |cpu, value| {
+            let result = value >> 1;
+            cpu.registers.set_flag(StatusFlag::Carry, value & 0x01 != 0);
+            cpu.registers.update_nz_flags(result, wide);
+            result
+        });
+    }
+
+    /// Moves a single byte of a block from bank:X to bank:Y and decrements C.
+    /// The instruction repeats itself until C wraps around to $FFFF, so that
+    /// interrupts can be serviced in the middle of a move.
+    fn block_move(&mut self, step: u16) {
+        let destination = self.read_program_byte(1);
+        let source = self.read_program_byte(2);
+
+        let value = self
+            .memory
+            .read_byte((source as u32) << 16 | self.registers.x as u32);
+        self.memory
+            .write_byte((destination as u32) << 16 | self.registers.y as u32, value);
+        self.registers.dbr = destination;
+
+        let x = self.registers.x.wrapping_add(step);
+        let y = self.registers.y.wrapping_add(step);
+        if self.registers.is_index_wide() {
+            self.registers.x = x;
+            self.registers.y = y;
+        } else {
+            self.registers.x = x & 0x00FF;
+            self.registers.y = y & 0x00FF;
+        }
+
+        self.registers.a = self.registers.a.wrapping_sub(1);
+        if self.registers.a == 0xFFFF {
+            self.registers.pc = self.registers.pc.wrapping_add(3);
+        }
+    }
+
+    fn mvn(&mut self, mode: AddressingMode) {
+        self.block_move(1);
+    }
+
+    fn mvp(&mut self, mode: AddressingMode) {
+        self.block_move(0xFFFF);
+    }
+
+    fn nop(&mut self, mode: AddressingMode) {}
+
+    fn ora(&mut self, mode: AddressingMode, wide: bool) {
+        let value = self.registers.accumulator() | self.read_operand(mode, wide);
+        self.registers.set_accumulator(value);
+        self.registers.update_nz_flags(value, wide);
+    }
+
+    fn pea(&mut self, mode: AddressingMode) {
+        let value = self.read_program_word(1);
+        self.push_word(value);
+    }
+
+    /// Pushes the word stored at the direct page operand.
+    fn pei(&mut self, mode: AddressingMode) {
+        let (address, _) = self.effective_address(mode);
+        if self.registers.d & 0x00FF != 0 {
+            self.penalty_cycles += 1;
+        }
+        let value = self.read_bank_zero_word(address);
+        self.push_word(value);
+    }
+
+    /// Pushes the address of the next instruction plus a 16-bit displacement.
+    fn per(&mut self, mode: AddressingMode) {
+        let displacement = self.read_program_word(1);
+        let value = self.registers.pc.wrapping_add(3).wrapping_add(displacement);
+        self.push_word(value);
+    }
+
+    fn pha(&mut self, mode: AddressingMode, wide: bool) {
+        self.push_value(self.registers.accumulator(), wide);
+        self.penalty_cycles += wide as u8;
+    }
+
+    fn phb(&mut self, mode: AddressingMode) {
+        self.push_byte(self.registers.dbr);
+    }
+
+    fn phd(&mut self, mode: AddressingMode) {
+        self.push_word(self.registers.d);
+    }
+
+    fn phk(&mut self, mode: AddressingMode) {
+        self.push_byte(self.registers.pbr);
+    }
+
+    fn php(&mut self, mode: AddressingMode) {
+        self.push_status(true);
+    }
+
+    fn phx(&mut self, mode: AddressingMode, wide: bool) {
+        self.push_value(self.registers.x, wide);
+        self.penalty_cycles += wide as u8;
+    }
+
+    fn phy(&mut self, mode: AddressingMode, wide: bool) {
+        self.push_value(self.registers.y, wide);
+        self.penalty_cycles += wide as u8;
+    }
+
+    fn pla(&mut self, mode: AddressingMode, wide: bool) {
+        let value = self.pull_value(wide);
+        self.penalty_cycles += wide as u8;
+        self.registers.set_accumulator(value);
+        self.registers.update_nz_flags(value, wide);
+    }
+
+    fn plb(&mut self, mode: AddressingMode) {
+        self.registers.dbr = self.pull_byte();
+        self.registers
+            .update_nz_flags(self.registers.dbr as u16, false);
+    }
+
+    fn pld(&mut self, mode: AddressingMode) {
+        self.registers.d = self.pull_word();
+        self.registers.update_nz_flags(self.registers.d, true);
+    }
+
+    fn plp(&mut self, mode: AddressingMode) {
+        self.pull_status();
+    }
+
+    fn plx(&mut self, mode: AddressingMode, wide: bool) {
+        let value = self.pull_value(wide);
+        self.penalty_cycles += wide as u8;
+        self.set_x(value);
+    }
+
+    fn ply(&mut self, mode: AddressingMode, wide: bool) {
+        let value = self.pull_value(wide);
+        self.penalty_cycles += wide as u8;
+        self.set_y(value);
+    }
+
+    /// Clears the bits of P that are set in the operand.
+    fn rep(&mut self, mode: AddressingMode) {
+        let mask = self.read_program_byte(1);
+        self.registers.set_status(self.registers.p & !mask);
+    }
+
+    fn rol(&mut self, mode: AddressingMode, wide: bool) {
+        self.modify(mode, wide, |cpu, value| {
+            let carry = cpu.registers.get_flag(StatusFlag::Carry) as u16;
+            let result = value << 1 | carry;
+            cpu.registers
+                .set_flag(StatusFlag::Carry, value & Cpu::sign(wide) != 0);
+            cpu.registers.update_nz_flags(result, wide);
+            result
+        });
+    }
+
+    fn ror(&mut self, mode: AddressingMode, wide: bool) {
+        self.modify(mode, wide, |cpu, value| {
+            let carry = cpu.registers.get_flag(StatusFlag::Carry);
+            let mut result = value >> 1;
+            if carry {
+                result |= Cpu::sign(wide);
+            }
+            cpu.registers.set_flag(StatusFlag::Carry, value & 0x01 != 0);
+            cpu.registers.update_nz_flags(result, wide);
+            result
+        });
+    }
+
+    /// Native mode also pulls the program bank, which costs one more cycle.
+    fn rti(&mut self, mode: AddressingMode) {
+        self.pull_status();
+        self.registers.pc = self.pull_word();
+        if !self.registers.emulation {
+            self.registers.pbr = self.pull_byte();
+            self.penalty_cycles += 1;
+        }
+    }
+
+    fn rtl(&mut self, mode: AddressingMode) {
+        self.registers.pc = self.pull_word().wrapping_add(1);
+        self.registers.pbr = self.pull_byte();
+    }
+
+    fn rts(&mut self, mode: AddressingMode) {
+        self.registers.pc = self.pull_word().wrapping_add(1);
+    }
+
+    fn sbc(&mut self, mode: AddressingMode, wide: bool) {
+        let operand = self.read_operand(mode, wide);
+        self.subtract(operand, wide);
+    }
+
+    fn sec(&mut self, mode: AddressingMode) {
+        self.registers.set_flag(StatusFlag::Carry, true);
+    }
+
+    fn sed(&mut self, mode: AddressingMode) {
+        self.registers.set_flag(StatusFlag::Decimal, true);
+    }
+
+    fn sei(&mut self, mode: AddressingMode) {
+        self.registers.set_flag(StatusFlag::NoInterrupts, true);
+    }
+
+    /// Sets the bits of P that are set in the operand.
+    fn sep(&mut self, mode: AddressingMode) {
+        let mask = self.read_program_byte(1);
+        self.registers.set_status(self.registers.p | mask);
+    }
+
+    fn sta(&mut self, mode: AddressingMode, wide: bool) {
+        self.write_operand(mode, self.registers.accumulator(), wide);
+    }
+
+    fn stp(&mut self, mode: AddressingMode) {
+        self.state = State::Stopped;
+    }
+
+    fn stx(&mut self, mode: AddressingMode, wide: bool) {
+        self.write_operand(mode, self.registers.x, wide);
+    }
+
+    fn sty(&mut self, mode: AddressingMode, wide: bool) {
+        self.write_operand(mode, self.registers.y, wide);
+    }
+
+    fn stz(&mut self, mode: AddressingMode, wide: bool) {
+        self.write_operand(mode, 0, wide);
+    }
+
+    fn tax(&mut self, mode: AddressingMode) {
+        self.set_x(self.registers.a);
+    }
+
+    fn tay(&mut self, mode: AddressingMode) {
+        self.set_y(self.registers.a);
+    }
+
+    /// TCD, TCS, TDC and TSC always transfer all 16 bits.
+    fn tcd(&mut self, mode: AddressingMode) {
+        self.registers.d = self.registers.a;
+        self.registers.update_nz_flags(self.registers.d, true);
+    }
+
+    fn tcs(&mut self, mode: AddressingMode) {
+        self.registers.sp = self.registers.a;
+        if self.registers.emulation {
+            self.registers.sp = STACK_LOW_ADDRESS | (self.registers.sp & 0x00FF);
+        }
+    }
+
+    fn tdc(&mut self, mode: AddressingMode) {
+        self.registers.a = self.registers.d;
+        self.registers.update_nz_flags(self.registers.a, true);
+    }
+
+    fn trb(&mut self, mode: AddressingMode, wide: bool) {
+        self.modify(mode, wide, |cpu, value| {
+            let a = cpu.registers.accumulator();
+            cpu.registers.set_flag(StatusFlag::Zero, a & value == 0);
+            value & !a
+        });
+    }
+
+    fn tsb(&mut self, mode: AddressingMode, wide: bool) {
+        self.modify(mode, wide, |cpu, value| {
+            let a = cpu.registers.accumulator();
+            cpu.registers.set_flag(StatusFlag::Zero, a & value == 0);
+            value | a
+        });
+    }
+
+    fn tsc(&mut self, mode: AddressingMode) {
+        self.registers.a = self.registers.sp;
+        self.registers.update_nz_flags(self.registers.a, true);
+    }
+
+    fn tsx(&mut self, mode: AddressingMode) {
+        self.set_x(self.registers.sp);
+    }
+
+    fn txa(&mut self, mode: AddressingMode) {
+        let wide = self.registers.is_accumulator_wide();
+        self.registers.set_accumulator(self.registers.x);
+        self.registers.update_nz_flags(self.registers.x, wide);
+    }
+
+    fn txs(&mut self, mode: AddressingMode) {
+        self.registers.sp = if self.registers.emulation {
+            STACK_LOW_ADDRESS | (self.registers.x & 0x00FF)
+        } else {
+            self.registers.x
+        };
+    }
+
+    fn txy(&mut self, mode: AddressingMode) {
+        self.set_y(self.registers.x);
+    }
+
+    fn tya(&mut self, mode: AddressingMode) {
+        let wide = self.registers.is_accumulator_wide();
+        self.registers.set_accumulator(self.registers.y);
+        self.registers.update_nz_flags(self.registers.y, wide);
+    }
+
+    fn tyx(&mut self, mode: AddressingMode) {
+        self.set_x(self.registers.y);
+    }
+
+    fn wai(&mut self, mode: AddressingMode) {
+        self.state = State::Waiting;
+    }
+
+    /// Reserved for future expansion, a two byte NOP on the 65C816.
+    fn wdm(&mut self, mode: AddressingMode) {}
+
+    fn xba(&mut self, mode: AddressingMode) {
+        self.registers.a = self.registers.a.swap_bytes();
+        self.registers.update_nz_flags(self.registers.a, false);
+    }
+
+    /// Exchanges the carry and emulation flags.
+    fn xce(&mut self, mode: AddressingMode) {
+        let carry = self.registers.get_flag(StatusFlag::Carry);
+        self.registers
+            .set_flag(StatusFlag::Carry, self.registers.emulation);
+        self.registers.set_emulation(carry);
+    }
+}
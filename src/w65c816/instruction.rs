@@ -0,0 +1,1762 @@
+#[derive(Copy, Debug, Clone, PartialEq, Eq)]
+pub enum AddressingMode {
+    Accumulator,
+    Implied,
+    Immediate,
+    Direct,
+    DirectX,
+    DirectY,
+    DirectIndirect,
+    DirectIndirectLong,
+    DirectIndexedIndirectX,
+    DirectIndirectIndexedY,
+    DirectIndirectLongIndexedY,
+    Relative,
+    RelativeLong,
+    Stack,
+    StackRelative,
+    StackRelativeIndirectIndexedY,
+    Absolute,
+    AbsoluteX,
+    AbsoluteY,
+    AbsoluteLong,
+    AbsoluteLongX,
+    AbsoluteIndexedIndirect,
+    AbsoluteIndirect,
+    AbsoluteIndirectLong,
+    BlockMove,
+}
+
+impl AddressingMode {
+    /// Whether the operand lives in the direct page, which costs an extra
+    /// cycle whenever the low byte of D is not zero.
+    pub fn uses_direct_page(self) -> bool {
+        matches!(
+            self,
+            AddressingMode::Direct
+                | AddressingMode::DirectX
+                | AddressingMode::DirectY
+                | AddressingMode::DirectIndirect
+                | AddressingMode::DirectIndirectLong
+                | AddressingMode::DirectIndexedIndirectX
+                | AddressingMode::DirectIndirectIndexedY
+                | AddressingMode::DirectIndirectLongIndexedY
+        )
+    }
+}
+
+#[derive(Copy, Debug, Clone, PartialEq, Eq)]
+pub enum OpCode {
+    ADC,
+    AND,
+    ASL,
+    BCC,
+    BCS,
+    BEQ,
+    BIT,
+    BMI,
+    BNE,
+    BPL,
+    BRA,
+    BRK,
+    BRL,
+    BVC,
+    BVS,
+    CLC,
+    CLD,
+    CLI,
+    CLV,
+    CMP,
+    COP,
+    CPX,
+    CPY,
+    DEC,
+    DEX,
+    DEY,
+    EOR,
+    INC,
+    INX,
+    INY,
+    JML,
+    JMP,
+    JSL,
+    JSR,
+    LDA,
+    LDX,
+    LDY,
+    LSR,
+    MVN,
+    MVP,
+    NOP,
+    ORA,
+    PEA,
+    PEI,
+    PER,
+    PHA,
+    PHB,
+    PHD,
+    PHK,
+    PHP,
+    PHX,
+    PHY,
+    PLA,
+    PLB,
+    PLD,
+    PLP,
+    PLX,
+    PLY,
+    REP,
+    ROL,
+    ROR,
+    RTI,
+    RTL,
+    RTS,
+    SBC,
+    SEC,
+    SED,
+    SEI,
+    SEP,
+    STA,
+    STP,
+    STX,
+    STY,
+    STZ,
+    TAX,
+    TAY,
+    TCD,
+    TCS,
+    TDC,
+    TRB,
+    TSB,
+    TSC,
+    TSX,
+    TXA,
+    TXS,
+    TXY,
+    TYA,
+    TYX,
+    WAI,
+    WDM,
+    XBA,
+    XCE,
+}
+
+impl OpCode {
+    /// Instructions whose data is as wide as the index registers rather
+    /// than the accumulator.
+    pub fn uses_index_registers(self) -> bool {
+        matches!(
+            self,
+            OpCode::CPX
+                | OpCode::CPY
+                | OpCode::LDX
+                | OpCode::LDY
+                | OpCode::PHX
+                | OpCode::PHY
+                | OpCode::PLX
+                | OpCode::PLY
+                | OpCode::STX
+                | OpCode::STY
+        )
+    }
+
+    /// Whether the instruction loads the program counter itself instead of
+    /// falling through to the next instruction. MVN and MVP are included, as
+    /// they execute again until the whole block has been moved.
+    pub fn transfers_control(self) -> bool {
+        matches!(
+            self,
+            OpCode::BCC
+                | OpCode::BCS
+                | OpCode::BEQ
+                | OpCode::BMI
+                | OpCode::BNE
+                | OpCode::BPL
+                | OpCode::BRA
+                | OpCode::BRK
+                | OpCode::BRL
+                | OpCode::BVC
+                | OpCode::BVS
+                | OpCode::COP
+                | OpCode::JML
+                | OpCode::JMP
+                | OpCode::JSL
+                | OpCode::JSR
+                | OpCode::MVN
+                | OpCode::MVP
+                | OpCode::RTI
+                | OpCode::RTL
+                | OpCode::RTS
+        )
+    }
+}
+
+#[derive(Copy, Debug, Clone)]
+pub struct Instruction {
+    pub opcode: OpCode,
+    pub mode: AddressingMode,
+    /// Encoded size in bytes with 8-bit registers, including the opcode.
+    pub length: u8,
+    /// Base cycle count, without any penalty cycles.
+    pub cycles: u8,
+}
+
+/// Decode table of the W65C816S, indexed by opcode byte.
+///
+/// Lengths and base cycle counts are those of an 8-bit accumulator and
+/// index registers. Immediate operands grow by a byte for 16-bit registers,
+/// which, like the other penalty cycles, is accounted for by the CPU.
+pub static OP_CODES: [Option<Instruction>; 256] = [
+    // 0x00
+    Some(Instruction {
+        opcode: OpCode::BRK,
+        mode: AddressingMode::Stack,
+        length: 2,
+        cycles: 7,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ORA,
+        mode: AddressingMode::DirectIndexedIndirectX,
+        length: 2,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::COP,
+        mode: AddressingMode::Stack,
+        length: 2,
+        cycles: 7,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ORA,
+        mode: AddressingMode::StackRelative,
+        length: 2,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::TSB,
+        mode: AddressingMode::Direct,
+        length: 2,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ORA,
+        mode: AddressingMode::Direct,
+        length: 2,
+        cycles: 3,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ASL,
+        mode: AddressingMode::Direct,
+        length: 2,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ORA,
+        mode: AddressingMode::DirectIndirectLong,
+        length: 2,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::PHP,
+        mode: AddressingMode::Stack,
+        length: 1,
+        cycles: 3,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ORA,
+        mode: AddressingMode::Immediate,
+        length: 2,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ASL,
+        mode: AddressingMode::Accumulator,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::PHD,
+        mode: AddressingMode::Stack,
+        length: 1,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::TSB,
+        mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ORA,
+        mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ASL,
+        mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ORA,
+        mode: AddressingMode::AbsoluteLong,
+        length: 4,
+        cycles: 5,
+    }),
+    // 0x10
+    Some(Instruction {
+        opcode: OpCode::BPL,
+        mode: AddressingMode::Relative,
+        length: 2,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ORA,
+        mode: AddressingMode::DirectIndirectIndexedY,
+        length: 2,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ORA,
+        mode: AddressingMode::DirectIndirect,
+        length: 2,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ORA,
+        mode: AddressingMode::StackRelativeIndirectIndexedY,
+        length: 2,
+        cycles: 7,
+    }),
+    Some(Instruction {
+        opcode: OpCode::TRB,
+        mode: AddressingMode::Direct,
+        length: 2,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ORA,
+        mode: AddressingMode::DirectX,
+        length: 2,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ASL,
+        mode: AddressingMode::DirectX,
+        length: 2,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ORA,
+        mode: AddressingMode::DirectIndirectLongIndexedY,
+        length: 2,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::CLC,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ORA,
+        mode: AddressingMode::AbsoluteY,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::INC,
+        mode: AddressingMode::Accumulator,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::TCS,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::TRB,
+        mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ORA,
+        mode: AddressingMode::AbsoluteX,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ASL,
+        mode: AddressingMode::AbsoluteX,
+        length: 3,
+        cycles: 7,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ORA,
+        mode: AddressingMode::AbsoluteLongX,
+        length: 4,
+        cycles: 5,
+    }),
+    // 0x20
+    Some(Instruction {
+        opcode: OpCode::JSR,
+        mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::AND,
+        mode: AddressingMode::DirectIndexedIndirectX,
+        length: 2,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::JSL,
+        mode: AddressingMode::AbsoluteLong,
+        length: 4,
+        cycles: 8,
+    }),
+    Some(Instruction {
+        opcode: OpCode::AND,
+        mode: AddressingMode::StackRelative,
+        length: 2,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::BIT,
+        mode: AddressingMode::Direct,
+        length: 2,
+        cycles: 3,
+    }),
+    Some(Instruction {
+        opcode: OpCode::AND,
+        mode: AddressingMode::Direct,
+        length: 2,
+        cycles: 3,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ROL,
+        mode: AddressingMode::Direct,
+        length: 2,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::AND,
+        mode: AddressingMode::DirectIndirectLong,
+        length: 2,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::PLP,
+        mode: AddressingMode::Stack,
+        length: 1,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::AND,
+        mode: AddressingMode::Immediate,
+        length: 2,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ROL,
+        mode: AddressingMode::Accumulator,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::PLD,
+        mode: AddressingMode::Stack,
+        length: 1,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::BIT,
+        mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::AND,
+        mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ROL,
+        mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::AND,
+        mode: AddressingMode::AbsoluteLong,
+        length: 4,
+        cycles: 5,
+    }),
+    // 0x30
+    Some(Instruction {
+        opcode: OpCode::BMI,
+        mode: AddressingMode::Relative,
+        length: 2,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::AND,
+        mode: AddressingMode::DirectIndirectIndexedY,
+        length: 2,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::AND,
+        mode: AddressingMode::DirectIndirect,
+        length: 2,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::AND,
+        mode: AddressingMode::StackRelativeIndirectIndexedY,
+        length: 2,
+        cycles: 7,
+    }),
+    Some(Instruction {
+        opcode: OpCode::BIT,
+        mode: AddressingMode::DirectX,
+        length: 2,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::AND,
+        mode: AddressingMode::DirectX,
+        length: 2,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ROL,
+        mode: AddressingMode::DirectX,
+        length: 2,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::AND,
+        mode: AddressingMode::DirectIndirectLongIndexedY,
+        length: 2,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::SEC,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::AND,
+        mode: AddressingMode::AbsoluteY,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::DEC,
+        mode: AddressingMode::Accumulator,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::TSC,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::BIT,
+        mode: AddressingMode::AbsoluteX,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::AND,
+        mode: AddressingMode::AbsoluteX,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ROL,
+        mode: AddressingMode::AbsoluteX,
+        length: 3,
+        cycles: 7,
+    }),
+    Some(Instruction {
+        opcode: OpCode::AND,
+        mode: AddressingMode::AbsoluteLongX,
+        length: 4,
+        cycles: 5,
+    }),
+    // 0x40
+    Some(Instruction {
+        opcode: OpCode::RTI,
+        mode: AddressingMode::Stack,
+        length: 1,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::EOR,
+        mode: AddressingMode::DirectIndexedIndirectX,
+        length: 2,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::WDM,
+        mode: AddressingMode::Implied,
+        length: 2,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::EOR,
+        mode: AddressingMode::StackRelative,
+        length: 2,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::MVP,
+        mode: AddressingMode::BlockMove,
+        length: 3,
+        cycles: 7,
+    }),
+    Some(Instruction {
+        opcode: OpCode::EOR,
+        mode: AddressingMode::Direct,
+        length: 2,
+        cycles: 3,
+    }),
+    Some(Instruction {
+        opcode: OpCode::LSR,
+        mode: AddressingMode::Direct,
+        length: 2,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::EOR,
+        mode: AddressingMode::DirectIndirectLong,
+        length: 2,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::PHA,
+        mode: AddressingMode::Stack,
+        length: 1,
+        cycles: 3,
+    }),
+    Some(Instruction {
+        opcode: OpCode::EOR,
+        mode: AddressingMode::Immediate,
+        length: 2,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::LSR,
+        mode: AddressingMode::Accumulator,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::PHK,
+        mode: AddressingMode::Stack,
+        length: 1,
+        cycles: 3,
+    }),
+    Some(Instruction {
+        opcode: OpCode::JMP,
+        mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 3,
+    }),
+    Some(Instruction {
+        opcode: OpCode::EOR,
+        mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::LSR,
+        mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::EOR,
+        mode: AddressingMode::AbsoluteLong,
+        length: 4,
+        cycles: 5,
+    }),
+    // 0x50
+    Some(Instruction {
+        opcode: OpCode::BVC,
+        mode: AddressingMode::Relative,
+        length: 2,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::EOR,
+        mode: AddressingMode::DirectIndirectIndexedY,
+        length: 2,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::EOR,
+        mode: AddressingMode::DirectIndirect,
+        length: 2,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::EOR,
+        mode: AddressingMode::StackRelativeIndirectIndexedY,
+        length: 2,
+        cycles: 7,
+    }),
+    Some(Instruction {
+        opcode: OpCode::MVN,
+        mode: AddressingMode::BlockMove,
+        length: 3,
+        cycles: 7,
+    }),
+    Some(Instruction {
+        opcode: OpCode::EOR,
+        mode: AddressingMode::DirectX,
+        length: 2,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::LSR,
+        mode: AddressingMode::DirectX,
+        length: 2,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::EOR,
+        mode: AddressingMode::DirectIndirectLongIndexedY,
+        length: 2,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::CLI,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::EOR,
+        mode: AddressingMode::AbsoluteY,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::PHY,
+        mode: AddressingMode::Stack,
+        length: 1,
+        cycles: 3,
+    }),
+    Some(Instruction {
+        opcode: OpCode::TCD,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::JML,
+        mode: AddressingMode::AbsoluteLong,
+        length: 4,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::EOR,
+        mode: AddressingMode::AbsoluteX,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::LSR,
+        mode: AddressingMode::AbsoluteX,
+        length: 3,
+        cycles: 7,
+    }),
+    Some(Instruction {
+        opcode: OpCode::EOR,
+        mode: AddressingMode::AbsoluteLongX,
+        length: 4,
+        cycles: 5,
+    }),
+    // 0x60
+    Some(Instruction {
+        opcode: OpCode::RTS,
+        mode: AddressingMode::Stack,
+        length: 1,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ADC,
+        mode: AddressingMode::DirectIndexedIndirectX,
+        length: 2,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::PER,
+        mode: AddressingMode::Stack,
+        length: 3,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ADC,
+        mode: AddressingMode::StackRelative,
+        length: 2,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::STZ,
+        mode: AddressingMode::Direct,
+        length: 2,
+        cycles: 3,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ADC,
+        mode: AddressingMode::Direct,
+        length: 2,
+        cycles: 3,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ROR,
+        mode: AddressingMode::Direct,
+        length: 2,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ADC,
+        mode: AddressingMode::DirectIndirectLong,
+        length: 2,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::PLA,
+        mode: AddressingMode::Stack,
+        length: 1,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ADC,
+        mode: AddressingMode::Immediate,
+        length: 2,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ROR,
+        mode: AddressingMode::Accumulator,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::RTL,
+        mode: AddressingMode::Stack,
+        length: 1,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::JMP,
+        mode: AddressingMode::AbsoluteIndirect,
+        length: 3,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ADC,
+        mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ROR,
+        mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ADC,
+        mode: AddressingMode::AbsoluteLong,
+        length: 4,
+        cycles: 5,
+    }),
+    // 0x70
+    Some(Instruction {
+        opcode: OpCode::BVS,
+        mode: AddressingMode::Relative,
+        length: 2,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ADC,
+        mode: AddressingMode::DirectIndirectIndexedY,
+        length: 2,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ADC,
+        mode: AddressingMode::DirectIndirect,
+        length: 2,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ADC,
+        mode: AddressingMode::StackRelativeIndirectIndexedY,
+        length: 2,
+        cycles: 7,
+    }),
+    Some(Instruction {
+        opcode: OpCode::STZ,
+        mode: AddressingMode::DirectX,
+        length: 2,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ADC,
+        mode: AddressingMode::DirectX,
+        length: 2,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ROR,
+        mode: AddressingMode::DirectX,
+        length: 2,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ADC,
+        mode: AddressingMode::DirectIndirectLongIndexedY,
+        length: 2,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::SEI,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ADC,
+        mode: AddressingMode::AbsoluteY,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::PLY,
+        mode: AddressingMode::Stack,
+        length: 1,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::TDC,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::JMP,
+        mode: AddressingMode::AbsoluteIndexedIndirect,
+        length: 3,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ADC,
+        mode: AddressingMode::AbsoluteX,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ROR,
+        mode: AddressingMode::AbsoluteX,
+        length: 3,
+        cycles: 7,
+    }),
+    Some(Instruction {
+        opcode: OpCode::ADC,
+        mode: AddressingMode::AbsoluteLongX,
+        length: 4,
+        cycles: 5,
+    }),
+    // 0x80
+    Some(Instruction {
+        opcode: OpCode::BRA,
+        mode: AddressingMode::Relative,
+        length: 2,
+        cycles: 3,
+    }),
+    Some(Instruction {
+        opcode: OpCode::STA,
+        mode: AddressingMode::DirectIndexedIndirectX,
+        length: 2,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::BRL,
+        mode: AddressingMode::RelativeLong,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::STA,
+        mode: AddressingMode::StackRelative,
+        length: 2,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::STY,
+        mode: AddressingMode::Direct,
+        length: 2,
+        cycles: 3,
+    }),
+    Some(Instruction {
+        opcode: OpCode::STA,
+        mode: AddressingMode::Direct,
+        length: 2,
+        cycles: 3,
+    }),
+    Some(Instruction {
+        opcode: OpCode::STX,
+        mode: AddressingMode::Direct,
+        length: 2,
+        cycles: 3,
+    }),
+    Some(Instruction {
+        opcode: OpCode::STA,
+        mode: AddressingMode::DirectIndirectLong,
+        length: 2,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::DEY,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::BIT,
+        mode: AddressingMode::Immediate,
+        length: 2,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::TXA,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::PHB,
+        mode: AddressingMode::Stack,
+        length: 1,
+        cycles: 3,
+    }),
+    Some(Instruction {
+        opcode: OpCode::STY,
+        mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::STA,
+        mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::STX,
+        mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::STA,
+        mode: AddressingMode::AbsoluteLong,
+        length: 4,
+        cycles: 5,
+    }),
+    // 0x90
+    Some(Instruction {
+        opcode: OpCode::BCC,
+        mode: AddressingMode::Relative,
+        length: 2,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::STA,
+        mode: AddressingMode::DirectIndirectIndexedY,
+        length: 2,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::STA,
+        mode: AddressingMode::DirectIndirect,
+        length: 2,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::STA,
+        mode: AddressingMode::StackRelativeIndirectIndexedY,
+        length: 2,
+        cycles: 7,
+    }),
+    Some(Instruction {
+        opcode: OpCode::STY,
+        mode: AddressingMode::DirectX,
+        length: 2,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::STA,
+        mode: AddressingMode::DirectX,
+        length: 2,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::STX,
+        mode: AddressingMode::DirectY,
+        length: 2,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::STA,
+        mode: AddressingMode::DirectIndirectLongIndexedY,
+        length: 2,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::TYA,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::STA,
+        mode: AddressingMode::AbsoluteY,
+        length: 3,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::TXS,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::TXY,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::STZ,
+        mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::STA,
+        mode: AddressingMode::AbsoluteX,
+        length: 3,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::STZ,
+        mode: AddressingMode::AbsoluteX,
+        length: 3,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::STA,
+        mode: AddressingMode::AbsoluteLongX,
+        length: 4,
+        cycles: 5,
+    }),
+    // 0xa0
+    Some(Instruction {
+        opcode: OpCode::LDY,
+        mode: AddressingMode::Immediate,
+        length: 2,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::LDA,
+        mode: AddressingMode::DirectIndexedIndirectX,
+        length: 2,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::LDX,
+        mode: AddressingMode::Immediate,
+        length: 2,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::LDA,
+        mode: AddressingMode::StackRelative,
+        length: 2,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::LDY,
+        mode: AddressingMode::Direct,
+        length: 2,
+        cycles: 3,
+    }),
+    Some(Instruction {
+        opcode: OpCode::LDA,
+        mode: AddressingMode::Direct,
+        length: 2,
+        cycles: 3,
+    }),
+    Some(Instruction {
+        opcode: OpCode::LDX,
+        mode: AddressingMode::Direct,
+        length: 2,
+        cycles: 3,
+    }),
+    Some(Instruction {
+        opcode: OpCode::LDA,
+        mode: AddressingMode::DirectIndirectLong,
+        length: 2,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::TAY,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::LDA,
+        mode: AddressingMode::Immediate,
+        length: 2,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::TAX,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::PLB,
+        mode: AddressingMode::Stack,
+        length: 1,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::LDY,
+        mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::LDA,
+        mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::LDX,
+        mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::LDA,
+        mode: AddressingMode::AbsoluteLong,
+        length: 4,
+        cycles: 5,
+    }),
+    // 0xb0
+    Some(Instruction {
+        opcode: OpCode::BCS,
+        mode: AddressingMode::Relative,
+        length: 2,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::LDA,
+        mode: AddressingMode::DirectIndirectIndexedY,
+        length: 2,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::LDA,
+        mode: AddressingMode::DirectIndirect,
+        length: 2,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::LDA,
+        mode: AddressingMode::StackRelativeIndirectIndexedY,
+        length: 2,
+        cycles: 7,
+    }),
+    Some(Instruction {
+        opcode: OpCode::LDY,
+        mode: AddressingMode::DirectX,
+        length: 2,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::LDA,
+        mode: AddressingMode::DirectX,
+        length: 2,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::LDX,
+        mode: AddressingMode::DirectY,
+        length: 2,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::LDA,
+        mode: AddressingMode::DirectIndirectLongIndexedY,
+        length: 2,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::CLV,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::LDA,
+        mode: AddressingMode::AbsoluteY,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::TSX,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::TYX,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::LDY,
+        mode: AddressingMode::AbsoluteX,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::LDA,
+        mode: AddressingMode::AbsoluteX,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::LDX,
+        mode: AddressingMode::AbsoluteY,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::LDA,
+        mode: AddressingMode::AbsoluteLongX,
+        length: 4,
+        cycles: 5,
+    }),
+    // 0xc0
+    Some(Instruction {
+        opcode: OpCode::CPY,
+        mode: AddressingMode::Immediate,
+        length: 2,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::CMP,
+        mode: AddressingMode::DirectIndexedIndirectX,
+        length: 2,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::REP,
+        mode: AddressingMode::Immediate,
+        length: 2,
+        cycles: 3,
+    }),
+    Some(Instruction {
+        opcode: OpCode::CMP,
+        mode: AddressingMode::StackRelative,
+        length: 2,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::CPY,
+        mode: AddressingMode::Direct,
+        length: 2,
+        cycles: 3,
+    }),
+    Some(Instruction {
+        opcode: OpCode::CMP,
+        mode: AddressingMode::Direct,
+        length: 2,
+        cycles: 3,
+    }),
+    Some(Instruction {
+        opcode: OpCode::DEC,
+        mode: AddressingMode::Direct,
+        length: 2,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::CMP,
+        mode: AddressingMode::DirectIndirectLong,
+        length: 2,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::INY,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::CMP,
+        mode: AddressingMode::Immediate,
+        length: 2,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::DEX,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::WAI,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 3,
+    }),
+    Some(Instruction {
+        opcode: OpCode::CPY,
+        mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::CMP,
+        mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::DEC,
+        mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::CMP,
+        mode: AddressingMode::AbsoluteLong,
+        length: 4,
+        cycles: 5,
+    }),
+    // 0xd0
+    Some(Instruction {
+        opcode: OpCode::BNE,
+        mode: AddressingMode::Relative,
+        length: 2,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::CMP,
+        mode: AddressingMode::DirectIndirectIndexedY,
+        length: 2,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::CMP,
+        mode: AddressingMode::DirectIndirect,
+        length: 2,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::CMP,
+        mode: AddressingMode::StackRelativeIndirectIndexedY,
+        length: 2,
+        cycles: 7,
+    }),
+    Some(Instruction {
+        opcode: OpCode::PEI,
+        mode: AddressingMode::Direct,
+        length: 2,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::CMP,
+        mode: AddressingMode::DirectX,
+        length: 2,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::DEC,
+        mode: AddressingMode::DirectX,
+        length: 2,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::CMP,
+        mode: AddressingMode::DirectIndirectLongIndexedY,
+        length: 2,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::CLD,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::CMP,
+        mode: AddressingMode::AbsoluteY,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::PHX,
+        mode: AddressingMode::Stack,
+        length: 1,
+        cycles: 3,
+    }),
+    Some(Instruction {
+        opcode: OpCode::STP,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 3,
+    }),
+    Some(Instruction {
+        opcode: OpCode::JML,
+        mode: AddressingMode::AbsoluteIndirectLong,
+        length: 3,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::CMP,
+        mode: AddressingMode::AbsoluteX,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::DEC,
+        mode: AddressingMode::AbsoluteX,
+        length: 3,
+        cycles: 7,
+    }),
+    Some(Instruction {
+        opcode: OpCode::CMP,
+        mode: AddressingMode::AbsoluteLongX,
+        length: 4,
+        cycles: 5,
+    }),
+    // 0xe0
+    Some(Instruction {
+        opcode: OpCode::CPX,
+        mode: AddressingMode::Immediate,
+        length: 2,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::SBC,
+        mode: AddressingMode::DirectIndexedIndirectX,
+        length: 2,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::SEP,
+        mode: AddressingMode::Immediate,
+        length: 2,
+        cycles: 3,
+    }),
+    Some(Instruction {
+        opcode: OpCode::SBC,
+        mode: AddressingMode::StackRelative,
+        length: 2,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::CPX,
+        mode: AddressingMode::Direct,
+        length: 2,
+        cycles: 3,
+    }),
+    Some(Instruction {
+        opcode: OpCode::SBC,
+        mode: AddressingMode::Direct,
+        length: 2,
+        cycles: 3,
+    }),
+    Some(Instruction {
+        opcode: OpCode::INC,
+        mode: AddressingMode::Direct,
+        length: 2,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::SBC,
+        mode: AddressingMode::DirectIndirectLong,
+        length: 2,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::INX,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::SBC,
+        mode: AddressingMode::Immediate,
+        length: 2,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::NOP,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::XBA,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 3,
+    }),
+    Some(Instruction {
+        opcode: OpCode::CPX,
+        mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::SBC,
+        mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::INC,
+        mode: AddressingMode::Absolute,
+        length: 3,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::SBC,
+        mode: AddressingMode::AbsoluteLong,
+        length: 4,
+        cycles: 5,
+    }),
+    // 0xf0
+    Some(Instruction {
+        opcode: OpCode::BEQ,
+        mode: AddressingMode::Relative,
+        length: 2,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::SBC,
+        mode: AddressingMode::DirectIndirectIndexedY,
+        length: 2,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::SBC,
+        mode: AddressingMode::DirectIndirect,
+        length: 2,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::SBC,
+        mode: AddressingMode::StackRelativeIndirectIndexedY,
+        length: 2,
+        cycles: 7,
+    }),
+    Some(Instruction {
+        opcode: OpCode::PEA,
+        mode: AddressingMode::Stack,
+        length: 3,
+        cycles: 5,
+    }),
+    Some(Instruction {
+        opcode: OpCode::SBC,
+        mode: AddressingMode::DirectX,
+        length: 2,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::INC,
+        mode: AddressingMode::DirectX,
+        length: 2,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::SBC,
+        mode: AddressingMode::DirectIndirectLongIndexedY,
+        length: 2,
+        cycles: 6,
+    }),
+    Some(Instruction {
+        opcode: OpCode::SED,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::SBC,
+        mode: AddressingMode::AbsoluteY,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::PLX,
+        mode: AddressingMode::Stack,
+        length: 1,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::XCE,
+        mode: AddressingMode::Implied,
+        length: 1,
+        cycles: 2,
+    }),
+    Some(Instruction {
+        opcode: OpCode::JSR,
+        mode: AddressingMode::AbsoluteIndexedIndirect,
+        length: 3,
+        cycles: 8,
+    }),
+    Some(Instruction {
+        opcode: OpCode::SBC,
+        mode: AddressingMode::AbsoluteX,
+        length: 3,
+        cycles: 4,
+    }),
+    Some(Instruction {
+        opcode: OpCode::INC,
+        mode: AddressingMode::AbsoluteX,
+        length: 3,
+        cycles: 7,
+    }),
+    Some(Instruction {
+        opcode: OpCode::SBC,
+        mode: AddressingMode::AbsoluteLongX,
+        length: 4,
+        cycles: 5,
+    }),
+];
@@ -0,0 +1,62 @@
+pub const MEMORY_SIZE: usize = 16 * 1024 * 1024;
+
+pub const ADDRESS_MASK: u32 = 0xFF_FFFF;
+pub const STACK_LOW_ADDRESS: u16 = 0x100;
+
+// native mode vectors
+pub const COP_NATIVE: u16 = 0xFFE4;
+pub const BRK_NATIVE: u16 = 0xFFE6;
+pub const ABORT_NATIVE: u16 = 0xFFE8;
+pub const NMI_NATIVE: u16 = 0xFFEA;
+pub const IRQ_NATIVE: u16 = 0xFFEE;
+
+// emulation mode vectors, BRK shares the IRQ one like on the 6502
+pub const COP: u16 = 0xFFF4;
+pub const ABORT: u16 = 0xFFF8;
+pub const NMI: u16 = 0xFFFA;
+pub const RESET: u16 = 0xFFFC;
+pub const IRQ: u16 = 0xFFFE;
+
+/// The full 24-bit address space of the 65C816, as 256 banks of 64K.
+pub struct Memory {
+    bytes: Vec<u8>,
+}
+
+impl Default for Memory {
+    fn default() -> Memory {
+        Memory::new()
+    }
+}
+
+impl Memory {
+    pub fn new() -> Memory {
+        Memory {
+            bytes: vec![0; MEMORY_SIZE],
+        }
+    }
+
+    pub fn read_byte(&self, address: u32) -> u8 {
+        self.bytes[(address & ADDRESS_MASK) as usize]
+    }
+
+    pub fn write_byte(&mut self, address: u32, value: u8) {
+        self.bytes[(address & ADDRESS_MASK) as usize] = value;
+    }
+
+    pub fn read_word(&self, addr: u32) -> u16 {
+        let lower = self.read_byte(addr) as u16;
+        let upper = self.read_byte(addr.wrapping_add(1)) as u16;
+        upper << 8 | lower
+    }
+
+    pub fn write_word(&mut self, addr: u32, val: u16) {
+        self.write_byte(addr, val as u8);
+        self.write_byte(addr.wrapping_add(1), (val >> 8) as u8);
+    }
+
+    /// Reads a 24-bit little endian address.
+    pub fn read_long(&self, addr: u32) -> u32 {
+        let bank = self.read_byte(addr.wrapping_add(2)) as u32;
+        bank << 16 | self.read_word(addr) as u32
+    }
+}
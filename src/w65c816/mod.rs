@@ -0,0 +1,14 @@
+//! The W65C816S, the 16-bit successor of the W65C02S. It starts out in an
+//! emulation mode that runs 6502 code and switches to native mode with XCE.
+//!
+//! The core stands on its own rather than sharing `Bus`, `Memory` and the
+//! instruction enums with the 6502 family. Its addresses are 24 bits wide
+//! while `Bus` and every device behind it deal in 16, and most of its
+//! addressing modes, stack-relative and long indirect among them, and the
+//! operand widths set by M and X have no counterpart in the 6502 tables.
+//! Folding it in would put width and bank checks on the hot path of every
+//! 6502 instruction. Only `State` and `VolveError` are shared.
+pub mod cpu;
+pub mod instruction;
+pub mod memory;
+pub mod registers;
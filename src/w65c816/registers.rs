@@ -0,0 +1,127 @@
+pub enum StatusFlag {
+    Carry = 1 << 0,
+    Zero = 1 << 1,
+    NoInterrupts = 1 << 2,
+    Decimal = 1 << 3,
+    /// X: 8-bit index registers. Doubles as the B flag in emulation mode.
+    IndexRegisters = 1 << 4,
+    /// M: 8-bit accumulator and memory accesses.
+    Accumulator = 1 << 5,
+    Overflow = 1 << 6,
+    Negative = 1 << 7,
+}
+
+pub struct Registers {
+    /// The 16-bit C accumulator. With an 8-bit accumulator only the low byte
+    /// is A, the high byte is the hidden B accumulator swapped in by XBA.
+    pub a: u16,
+    pub x: u16,
+    pub y: u16,
+
+    pub p: u8,
+    pub sp: u16,
+    pub pc: u16,
+
+    /// Direct page register.
+    pub d: u16,
+    /// Data bank register.
+    pub dbr: u8,
+    /// Program bank register.
+    pub pbr: u8,
+    /// Emulation flag, only reachable through XCE.
+    pub emulation: bool,
+}
+
+impl Default for Registers {
+    fn default() -> Registers {
+        Registers::new()
+    }
+}
+
+impl Registers {
+    pub fn set_flag(&mut self, flag: StatusFlag, mode: bool) {
+        if mode {
+            self.p |= flag as u8;
+        } else {
+            self.p &= !(flag as u8);
+        }
+    }
+
+    pub fn get_flag(&self, flag: StatusFlag) -> bool {
+        (self.p & flag as u8) != 0
+    }
+
+    /// Loads P, keeping M and X set in emulation mode. Setting X clears the
+    /// high bytes of the index registers.
+    pub fn set_status(&mut self, value: u8) {
+        self.p = value;
+        if self.emulation {
+            self.p |= StatusFlag::Accumulator as u8 | StatusFlag::IndexRegisters as u8;
+        }
+        if self.get_flag(StatusFlag::IndexRegisters) {
+            self.x &= 0x00FF;
+            self.y &= 0x00FF;
+        }
+    }
+
+    /// Switches between emulation and native mode. Emulation mode forces
+    /// 8-bit registers and moves the stack back into page $01.
+    pub fn set_emulation(&mut self, emulation: bool) {
+        self.emulation = emulation;
+        if emulation {
+            self.sp = 0x0100 | (self.sp & 0x00FF);
+            self.set_status(self.p);
+        }
+    }
+
+    pub fn is_accumulator_wide(&self) -> bool {
+        !self.get_flag(StatusFlag::Accumulator)
+    }
+
+    pub fn is_index_wide(&self) -> bool {
+        !self.get_flag(StatusFlag::IndexRegisters)
+    }
+
+    /// A or C, depending on the accumulator width.
+    pub fn accumulator(&self) -> u16 {
+        if self.is_accumulator_wide() {
+            self.a
+        } else {
+            self.a & 0x00FF
+        }
+    }
+
+    /// Stores to A or C, depending on the accumulator width. An 8-bit store
+    /// leaves B untouched.
+    pub fn set_accumulator(&mut self, value: u16) {
+        if self.is_accumulator_wide() {
+            self.a = value;
+        } else {
+            self.a = (self.a & 0xFF00) | (value & 0x00FF);
+        }
+    }
+
+    pub fn update_nz_flags(&mut self, value: u16, wide: bool) {
+        let sign = if wide { 0x8000 } else { 0x0080 };
+        let value = if wide { value } else { value & 0x00FF };
+        self.set_flag(StatusFlag::Zero, value == 0);
+        self.set_flag(StatusFlag::Negative, value & sign != 0);
+    }
+
+    pub fn new() -> Registers {
+        Registers {
+            a: 0,
+            x: 0,
+            y: 0,
+
+            p: 0x34,
+            sp: 0x01FF,
+            pc: 0,
+
+            d: 0,
+            dbr: 0,
+            pbr: 0,
+            emulation: true,
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+use volve::bus::Bus;
+use volve::devices::acia::{self, Acia, Status};
+use volve::devices::serial::Serial;
+use volve::memory::Memory;
+
+const BASE: u16 = 0x5000;
+/// DTR set with the receiver interrupt enabled, transmitter interrupt off.
+const RX_IRQ: u8 = 0x09;
+
+/// The far end of the line, with bytes queued up for the ACIA to receive.
+#[derive(Clone, Default)]
+struct Loopback {
+    input: Rc<RefCell<VecDeque<u8>>>,
+}
+
+impl Serial for Loopback {
+    fn send(&mut self, _byte: u8) {}
+
+    fn receive(&mut self) -> Option<u8> {
+        self.input.borrow_mut().pop_front()
+    }
+}
+
+#[test]
+fn reading_status_acknowledges_receive_interrupt() {
+    let line = Loopback::default();
+    let mut memory = Memory::new();
+    memory.attach(BASE..=BASE + 3, Acia::new(line.clone()));
+    memory.write(BASE + acia::COMMAND, RX_IRQ);
+
+    line.input.borrow_mut().extend(b"AB");
+    memory.sync(1);
+    assert!(memory.irq());
+
+    let status = memory.read(BASE + acia::STATUS);
+    assert_ne!(status & Status::Interrupt as u8, 0);
+    assert_ne!(status & Status::ReceiverFull as u8, 0);
+    assert!(!memory.irq());
+
+    // the byte has not been read yet, which must not raise IRQ again
+    for cycles in 2..10 {
+        memory.sync(cycles);
+        assert!(!memory.irq());
+    }
+
+    // the next byte does, once the first one has been read
+    assert_eq!(memory.read(BASE + acia::DATA), b'A');
+    memory.sync(10);
+    assert!(memory.irq());
+    memory.read(BASE + acia::STATUS);
+    assert_eq!(memory.read(BASE + acia::DATA), b'B');
+    memory.sync(11);
+    assert!(!memory.irq());
+}
@@ -0,0 +1,140 @@
+use volve::bus::Bus;
+use volve::code::{self, Format, Image};
+use volve::device::{Context, Device};
+use volve::error::VolveError;
+use volve::map::MemoryMap;
+use volve::memory::{Memory, RESET};
+
+const FORMATS: [Format; 2] = [Format::IntelHex, Format::SRecord];
+
+/// A device that reads as $55 and ignores writes.
+struct Port;
+
+impl Device for Port {
+    fn read(&mut self, offset: u16, _context: &mut Context) -> u8 {
+        self.peek(offset)
+    }
+
+    fn write(&mut self, _offset: u16, _value: u8, _context: &mut Context) {}
+
+    fn peek(&self, _offset: u16) -> u8 {
+        0x55
+    }
+}
+
+/// 40 bytes at $0200, which span three records.
+fn program() -> Memory {
+    let mut memory = Memory::new();
+    let bytes: Vec<u8> = (0..40).map(|index| index * 3).collect();
+    code::load(&mut memory, 0x0200, &bytes).unwrap();
+    memory
+}
+
+/// Exports $0200-$0227 of `program` and loads it back into a machine whose
+/// reset vector already holds `vector`.
+fn round_trip(format: Format, start: Option<u16>, vector: u16) -> (Image, Memory) {
+    let text = code::export(&program(), 0x0200..=0x0227, start, format);
+    let image = Image::parse(&text, format).unwrap();
+
+    let mut memory = Memory::new();
+    memory.write_word(RESET, vector);
+    image.load(&mut memory).unwrap();
+    (image, memory)
+}
+
+#[test]
+fn round_trip_without_entry_point() {
+    for format in FORMATS {
+        let (image, memory) = round_trip(format, None, 0xC000);
+        let original = program();
+
+        assert_eq!(image.start, None, "{:?}", format);
+        assert!((0x0200..=0x0227).all(|address| memory.peek(address) == original.peek(address)));
+        // a partial image must leave the reset vector alone
+        assert_eq!(memory.peek_word(RESET), 0xC000, "{:?}", format);
+    }
+}
+
+#[test]
+fn round_trip_with_entry_point() {
+    for format in FORMATS {
+        let (image, memory) = round_trip(format, Some(0x0200), 0xC000);
+        assert_eq!(image.start, Some(0x0200), "{:?}", format);
+        // the vector of the ROM underneath wins
+        assert_eq!(memory.peek_word(RESET), 0xC000, "{:?}", format);
+
+        // an empty vector takes the entry point
+        for vector in [0x0000, 0xFFFF] {
+            let (_, memory) = round_trip(format, Some(0x0200), vector);
+            assert_eq!(memory.peek_word(RESET), 0x0200, "{:?}", format);
+        }
+    }
+}
+
+#[test]
+fn export_has_valid_checksums() {
+    let memory = program();
+    assert_eq!(
+        code::export(&memory, 0x0200..=0x0203, Some(0x0200), Format::IntelHex),
+        ":0402000000030609E8\n:0400000500000200F5\n:00000001FF\n"
+    );
+    assert_eq!(
+        code::export(&memory, 0x0200..=0x0203, Some(0x0200), Format::SRecord),
+        "S0030000FC\nS107020000030609E4\nS5030001FB\nS9030200FA\n"
+    );
+}
+
+#[test]
+fn corrupted_checksum_is_rejected() {
+    for format in FORMATS {
+        let text = code::export(&program(), 0x0200..=0x0227, None, format);
+        // flip a data bit in the first data record
+        let corrupted = text.replacen("0F", "0E", 1);
+        assert_ne!(text, corrupted);
+
+        match Image::parse(&corrupted, format) {
+            Err(VolveError::BadRecord { line, reason }) => {
+                assert_eq!(reason, "checksum mismatch");
+                assert!(line > 0);
+            }
+            result => panic!("{:?}: expected a bad record, got {:?}", format, result),
+        }
+    }
+}
+
+#[test]
+fn loading_over_a_device_fails() {
+    let mut memory = Memory::new();
+    memory.attach(0x0204..=0x0207, Port);
+
+    match code::load(&mut memory, 0x0200, &[0xAA; 8]) {
+        Err(VolveError::NotLoadable { address }) => assert_eq!(address, 0x0204),
+        result => panic!("expected an error, got {:?}", result),
+    }
+    // nothing is written, not even below the device
+    assert_eq!(memory.peek(0x0200), 0x00);
+    code::load(&mut memory, 0x0200, &[0xAA; 4]).unwrap();
+    assert_eq!(memory.peek(0x0203), 0xAA);
+}
+
+#[test]
+fn loading_into_unmapped_addresses_fails() {
+    let mut map = MemoryMap::builder()
+        .ram(0x0000..=0x0FFF)
+        .and_then(|builder| builder.mirror(0x1000..=0x1FFF, 0x0000..=0x0FFF))
+        .and_then(|builder| builder.unmapped(0x0800..=0x08FF))
+        .unwrap()
+        .build();
+
+    // through a mirror is fine
+    code::load(&mut map, 0x1000, &[0xAA; 4]).unwrap();
+    assert_eq!(map.peek(0x0003), 0xAA);
+
+    // into the hole, and past the end of the map
+    for (start, unmapped) in [(0x07FF, 0x0800), (0x1FFF, 0x2000)] {
+        match code::load(&mut map, start, &[0xAA; 2]) {
+            Err(VolveError::NotLoadable { address }) => assert_eq!(address, unmapped),
+            result => panic!("${:04X}: expected an error, got {:?}", start, result),
+        }
+    }
+}
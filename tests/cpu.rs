@@ -0,0 +1,217 @@
+use volve::bus::Bus;
+use volve::cpu::Cpu;
+use volve::memory::Memory;
+use volve::variant::CpuVariant;
+
+/// A plain memory that keeps a log of every bus access.
+#[derive(Default)]
+struct Recorder {
+    memory: Memory,
+    reads: Vec<u16>,
+    writes: Vec<(u16, u8)>,
+}
+
+impl Bus for Recorder {
+    fn read(&mut self, address: u16) -> u8 {
+        self.reads.push(address);
+        self.memory.read(address)
+    }
+
+    fn write(&mut self, address: u16, value: u8) {
+        self.writes.push((address, value));
+        self.memory.write(address, value);
+    }
+
+    fn peek(&self, address: u16) -> u8 {
+        self.memory.peek(address)
+    }
+}
+
+/// A CPU with `code` at $0200, ready to execute it.
+fn setup(variant: CpuVariant, code: &[u8]) -> Cpu<Recorder> {
+    let mut cpu = Cpu::with_bus(Recorder::default(), variant);
+    for (offset, &byte) in code.iter().enumerate() {
+        cpu.bus.memory.write_byte(0x0200 + offset as u16, byte);
+    }
+    cpu.registers.pc = 0x0200;
+    cpu
+}
+
+#[test]
+fn zero_page_indexing_wraps() {
+    // LDA $F0,X
+    let mut cpu = setup(CpuVariant::Wdc65C02, &[0xB5, 0xF0]);
+    cpu.bus.memory.write_byte(0x0010, 0x42);
+    cpu.bus.memory.write_byte(0x0110, 0x99);
+    cpu.registers.x = 0x20;
+
+    assert_eq!(cpu.step().unwrap(), 4);
+    assert_eq!(cpu.registers.a, 0x42);
+    assert_eq!(cpu.registers.pc, 0x0202);
+}
+
+#[test]
+fn indexed_indirect_pointer_wraps() {
+    // LDA ($FF,X), the pointer is read from $FF and $00
+    let mut cpu = setup(CpuVariant::Wdc65C02, &[0xA1, 0xFF]);
+    cpu.bus.memory.write_byte(0x00FF, 0x34);
+    cpu.bus.memory.write_byte(0x0000, 0x12);
+    cpu.bus.memory.write_byte(0x1234, 0x42);
+
+    assert_eq!(cpu.step().unwrap(), 6);
+    assert_eq!(cpu.registers.a, 0x42);
+}
+
+#[test]
+fn indirect_indexed_adds_y_after_the_pointer() {
+    // LDA ($10),Y
+    let mut cpu = setup(CpuVariant::Wdc65C02, &[0xB1, 0x10]);
+    cpu.bus.memory.write_word(0x0010, 0x1234);
+    cpu.bus.memory.write_byte(0x1238, 0x42);
+    cpu.registers.y = 4;
+
+    assert_eq!(cpu.step().unwrap(), 5);
+    assert_eq!(cpu.registers.a, 0x42);
+}
+
+#[test]
+fn zero_page_indirect() {
+    // LDA ($10), which only exists on the CMOS parts
+    let mut cpu = setup(CpuVariant::Wdc65C02, &[0xB2, 0x10]);
+    cpu.bus.memory.write_word(0x0010, 0x1234);
+    cpu.bus.memory.write_byte(0x1234, 0x42);
+
+    assert_eq!(cpu.step().unwrap(), 5);
+    assert_eq!(cpu.registers.a, 0x42);
+}
+
+#[test]
+fn jmp_indirect_page_boundary() {
+    // JMP ($02FF), the NMOS 6502 takes the high byte from $0200
+    let code = [0x6C, 0xFF, 0x02];
+    let expected = [
+        (CpuVariant::Nmos6502, 0x6C34, 5),
+        (CpuVariant::Wdc65C02, 0x1234, 6),
+    ];
+
+    for (variant, target, cycles) in expected {
+        let mut cpu = setup(variant, &code);
+        cpu.bus.memory.write_byte(0x02FF, 0x34);
+        cpu.bus.memory.write_byte(0x0300, 0x12);
+
+        assert_eq!(cpu.step().unwrap(), cycles, "{:?}", variant);
+        assert_eq!(cpu.registers.pc, target, "{:?}", variant);
+    }
+}
+
+#[test]
+fn page_cross_penalties() {
+    // (code, x and y, cycles without and with a page crossing)
+    let cases: [(&[u8], u8, u8, u8); 5] = [
+        // LDA $12F0,X
+        (&[0xBD, 0xF0, 0x12], 0x20, 4, 5),
+        // LDA $12F0,Y
+        (&[0xB9, 0xF0, 0x12], 0x20, 4, 5),
+        // LDA ($10),Y with $12F0 at $10
+        (&[0xB1, 0x10], 0x20, 5, 6),
+        // STA $12F0,X always takes the extra cycle
+        (&[0x9D, 0xF0, 0x12], 0x20, 5, 5),
+        // INC $12F0,X as well
+        (&[0xFE, 0xF0, 0x12], 0x20, 7, 7),
+    ];
+
+    for (code, index, same_page, crossed) in cases {
+        for (offset, cycles) in [(0x00, same_page), (index, crossed)] {
+            let mut cpu = setup(CpuVariant::Wdc65C02, code);
+            cpu.bus.memory.write_word(0x0010, 0x12F0);
+            cpu.registers.x = offset;
+            cpu.registers.y = offset;
+
+            assert_eq!(
+                cpu.step().unwrap(),
+                cycles,
+                "{:02X?} index {:02X}",
+                code,
+                offset
+            );
+        }
+    }
+}
+
+#[test]
+fn branch_penalties() {
+    // BNE from $0200 with Z clear, the next instruction is at $0202
+    for (displacement, cycles) in [(0x10, 3), (0xF0, 4)] {
+        let mut cpu = setup(CpuVariant::Wdc65C02, &[0xD0, displacement]);
+        cpu.registers.p &= !0x02;
+
+        assert_eq!(
+            cpu.step().unwrap(),
+            cycles,
+            "displacement {:02X}",
+            displacement
+        );
+        let target = 0x0202u16.wrapping_add(displacement as i8 as u16);
+        assert_eq!(cpu.registers.pc, target);
+    }
+
+    // not taken
+    let mut cpu = setup(CpuVariant::Wdc65C02, &[0xD0, 0x7F]);
+    cpu.registers.p |= 0x02;
+    assert_eq!(cpu.step().unwrap(), 2);
+    assert_eq!(cpu.registers.pc, 0x0202);
+}
+
+#[test]
+fn read_modify_write_accesses() {
+    // INC $0300
+    let code = [0xEE, 0x00, 0x03];
+
+    let mut cpu = setup(CpuVariant::Nmos6502, &code);
+    cpu.bus.memory.write_byte(0x0300, 0x41);
+    cpu.step().unwrap();
+    assert_eq!(cpu.bus.writes, [(0x0300, 0x41), (0x0300, 0x42)]);
+
+    let mut cpu = setup(CpuVariant::Wdc65C02, &code);
+    cpu.bus.memory.write_byte(0x0300, 0x41);
+    cpu.step().unwrap();
+    assert_eq!(cpu.bus.writes, [(0x0300, 0x42)]);
+    let operand_reads = cpu.bus.reads.iter().filter(|&&address| address == 0x0300);
+    assert_eq!(operand_reads.count(), 2);
+}
+
+#[test]
+fn stack_wraps_within_page_one() {
+    // PHA, PLA
+    let mut cpu = setup(CpuVariant::Wdc65C02, &[0x48, 0x68]);
+    cpu.registers.sp = 0x00;
+    cpu.registers.a = 0x42;
+
+    cpu.step().unwrap();
+    assert_eq!(cpu.bus.writes, [(0x0100, 0x42)]);
+    assert_eq!(cpu.registers.sp, 0xFF);
+
+    cpu.registers.a = 0;
+    cpu.step().unwrap();
+    assert_eq!(cpu.registers.a, 0x42);
+    assert_eq!(cpu.registers.sp, 0x00);
+}
+
+#[test]
+fn jsr_pushes_across_the_stack_wrap() {
+    // JSR $1234 with one byte left on the stack
+    let mut cpu = setup(CpuVariant::Wdc65C02, &[0x20, 0x34, 0x12]);
+    cpu.registers.sp = 0x00;
+
+    assert_eq!(cpu.step().unwrap(), 6);
+    assert_eq!(cpu.registers.pc, 0x1234);
+    // the return address is the last byte of the JSR
+    assert_eq!(cpu.bus.writes, [(0x0100, 0x02), (0x01FF, 0x02)]);
+    assert_eq!(cpu.registers.sp, 0xFE);
+
+    // RTS
+    cpu.bus.memory.write_byte(0x1234, 0x60);
+    assert_eq!(cpu.step().unwrap(), 6);
+    assert_eq!(cpu.registers.pc, 0x0203);
+    assert_eq!(cpu.registers.sp, 0x00);
+}
@@ -0,0 +1,247 @@
+use volve::cpu::Cpu;
+use volve::registers::StatusFlag;
+use volve::variant::CpuVariant;
+
+const CARRY: u8 = StatusFlag::Carry as u8;
+const ZERO: u8 = StatusFlag::Zero as u8;
+const OVERFLOW: u8 = StatusFlag::Overflow as u8;
+const NEGATIVE: u8 = StatusFlag::Negative as u8;
+
+/// Runs `opcode #operand` in decimal mode and returns A and the flags
+/// (N, V, Z and C only).
+fn execute(opcode: u8, a: u8, operand: u8, carry: bool) -> (u8, u8) {
+    execute_on(CpuVariant::Wdc65C02, opcode, a, operand, carry)
+}
+
+fn execute_on(variant: CpuVariant, opcode: u8, a: u8, operand: u8, carry: bool) -> (u8, u8) {
+    let mut cpu = Cpu::with_variant(variant);
+    cpu.bus.write_byte(0x0200, opcode);
+    cpu.bus.write_byte(0x0201, operand);
+    cpu.registers.pc = 0x0200;
+    cpu.registers.a = a;
+    cpu.registers.set_flag(StatusFlag::Decimal, true);
+    cpu.registers.set_flag(StatusFlag::Carry, carry);
+
+    cpu.step().unwrap();
+
+    assert_eq!(cpu.registers.pc, 0x0202);
+    let flags = cpu.registers.p & (NEGATIVE | OVERFLOW | ZERO | CARRY);
+    (cpu.registers.a, flags)
+}
+
+fn flags(result: u8, overflow: bool, carry: bool) -> u8 {
+    let mut flags = 0;
+    if result & 0x80 != 0 {
+        flags |= NEGATIVE;
+    }
+    if overflow {
+        flags |= OVERFLOW;
+    }
+    if result == 0 {
+        flags |= ZERO;
+    }
+    if carry {
+        flags |= CARRY;
+    }
+    flags
+}
+
+#[test]
+fn adc_known_values() {
+    assert_eq!(execute(0x69, 0x12, 0x34, false), (0x46, 0));
+    assert_eq!(execute(0x69, 0x58, 0x46, true), (0x05, OVERFLOW | CARRY));
+    assert_eq!(execute(0x69, 0x81, 0x92, false), (0x73, OVERFLOW | CARRY));
+    assert_eq!(execute(0x69, 0x99, 0x01, false), (0x00, ZERO | CARRY));
+    assert_eq!(execute(0x69, 0x79, 0x00, true), (0x80, NEGATIVE | OVERFLOW));
+}
+
+#[test]
+fn sbc_known_values() {
+    assert_eq!(execute(0xE9, 0x46, 0x12, true), (0x34, CARRY));
+    assert_eq!(execute(0xE9, 0x40, 0x13, true), (0x27, CARRY));
+    assert_eq!(execute(0xE9, 0x32, 0x02, false), (0x29, CARRY));
+    assert_eq!(execute(0xE9, 0x12, 0x21, true), (0x91, NEGATIVE));
+    assert_eq!(execute(0xE9, 0x21, 0x21, true), (0x00, ZERO | CARRY));
+}
+
+/// Value of a valid BCD byte.
+fn from_bcd(value: u8) -> i32 {
+    (value >> 4) as i32 * 10 + (value & 0x0F) as i32
+}
+
+fn to_bcd(value: i32) -> u8 {
+    (((value / 10) << 4) | (value % 10)) as u8
+}
+
+/// All valid BCD bytes, $00 to $99.
+fn bcd_values() -> impl Iterator<Item = u8> {
+    (0..100).map(to_bcd)
+}
+
+/// The results of valid BCD inputs follow from plain decimal arithmetic.
+/// V after ADC is left out, as it is not documented for decimal mode.
+#[test]
+fn adc_valid_bcd() {
+    for carry in [false, true] {
+        for a in bcd_values() {
+            for b in bcd_values() {
+                let sum = from_bcd(a) + from_bcd(b) + carry as i32;
+                let result = to_bcd(sum % 100);
+                let binary = a.wrapping_add(b).wrapping_add(carry as u8);
+
+                let (cmos, cmos_flags) = execute(0x69, a, b, carry);
+                assert_eq!(
+                    (cmos, cmos_flags & !OVERFLOW),
+                    (result, flags(result, false, sum >= 100)),
+                    "65C02 ADC: A={:02x} M={:02x} C={}",
+                    a,
+                    b,
+                    carry
+                );
+
+                // the NMOS 6502 takes Z from the binary sum, and N from a
+                // half corrected one
+                let (nmos, nmos_flags) = execute_on(CpuVariant::Nmos6502, 0x69, a, b, carry);
+                assert_eq!(
+                    (nmos, nmos_flags & (ZERO | CARRY)),
+                    (result, flags(binary, false, sum >= 100) & (ZERO | CARRY)),
+                    "6502 ADC: A={:02x} M={:02x} C={}",
+                    a,
+                    b,
+                    carry
+                );
+            }
+        }
+    }
+}
+
+/// C and V after SBC are the same as in binary mode on both parts.
+#[test]
+fn sbc_valid_bcd() {
+    for carry in [false, true] {
+        for a in bcd_values() {
+            for b in bcd_values() {
+                let borrow = !carry as i32;
+                let difference = from_bcd(a) - from_bcd(b) - borrow;
+                let result = to_bcd((difference + 100) % 100);
+                let signed = a as i8 as i32 - b as i8 as i32 - borrow;
+                let overflow = !(-128..=127).contains(&signed);
+                let binary = (a as i32 - b as i32 - borrow) as u8;
+
+                assert_eq!(
+                    execute(0xE9, a, b, carry),
+                    (result, flags(result, overflow, difference >= 0)),
+                    "65C02 SBC: A={:02x} M={:02x} C={}",
+                    a,
+                    b,
+                    carry
+                );
+
+                // N and Z come from the binary difference on the NMOS 6502
+                assert_eq!(
+                    execute_on(CpuVariant::Nmos6502, 0xE9, a, b, carry),
+                    (result, flags(binary, overflow, difference >= 0)),
+                    "6502 SBC: A={:02x} M={:02x} C={}",
+                    a,
+                    b,
+                    carry
+                );
+            }
+        }
+    }
+}
+
+// Results worked out by hand following the sequences in Bruce Clark's
+// "Decimal Mode" tutorial, for the flags plain decimal arithmetic does not
+// define and for invalid BCD inputs: (opcode, A, M, C, A after, flags after).
+
+const CMOS_CASES: [(u8, u8, u8, bool, u8, u8); 8] = [
+    (0x69, 0x99, 0x01, false, 0x00, ZERO | CARRY),
+    (0x69, 0x79, 0x00, true, 0x80, NEGATIVE | OVERFLOW),
+    (0x69, 0x50, 0x50, false, 0x00, OVERFLOW | ZERO | CARRY),
+    (0x69, 0x0A, 0x00, false, 0x10, 0),
+    (0x69, 0x1C, 0x00, false, 0x22, 0),
+    (0x69, 0xFF, 0xFF, true, 0x55, CARRY),
+    (0xE9, 0x0A, 0x00, true, 0x0A, CARRY),
+    (0xE9, 0x80, 0x01, true, 0x79, OVERFLOW | CARRY),
+];
+
+const NMOS_CASES: [(u8, u8, u8, bool, u8, u8); 8] = [
+    (0x69, 0x99, 0x01, false, 0x00, NEGATIVE | CARRY),
+    (0x69, 0x79, 0x00, true, 0x80, NEGATIVE | OVERFLOW),
+    (0x69, 0x50, 0x50, false, 0x00, NEGATIVE | OVERFLOW | CARRY),
+    (0x69, 0x0A, 0x00, false, 0x10, 0),
+    (0x69, 0x1C, 0x00, false, 0x22, 0),
+    (0x69, 0xFF, 0xFF, true, 0x55, NEGATIVE | CARRY),
+    (0xE9, 0x00, 0x01, true, 0x99, NEGATIVE),
+    (0xE9, 0x80, 0x01, true, 0x79, OVERFLOW | CARRY),
+];
+
+#[test]
+fn known_flags_and_invalid_bcd() {
+    for (variant, cases) in [
+        (CpuVariant::Wdc65C02, CMOS_CASES),
+        (CpuVariant::Nmos6502, NMOS_CASES),
+    ] {
+        for (opcode, a, b, carry, result, flags) in cases {
+            assert_eq!(
+                execute_on(variant, opcode, a, b, carry),
+                (result, flags),
+                "{:?} {:02x}: A={:02x} M={:02x} C={}",
+                variant,
+                opcode,
+                a,
+                b,
+                carry
+            );
+        }
+    }
+}
+
+/// Cycles `opcode #$01` takes with the D flag clear and set.
+fn decimal_cycles(variant: CpuVariant, opcode: u8) -> (u8, u8) {
+    let cycles = |decimal| {
+        let mut cpu = Cpu::with_variant(variant);
+        cpu.bus.write_byte(0x0200, opcode);
+        cpu.bus.write_byte(0x0201, 0x01);
+        cpu.registers.pc = 0x0200;
+        cpu.registers.set_flag(StatusFlag::Decimal, decimal);
+        cpu.step().unwrap()
+    };
+    (cycles(false), cycles(true))
+}
+
+#[test]
+fn decimal_mode_cycles() {
+    // only the CMOS parts spend an extra cycle on fixing up N and Z
+    let expected = [
+        (CpuVariant::Wdc65C02, (2, 3)),
+        (CpuVariant::Nmos6502, (2, 2)),
+        (CpuVariant::Ricoh2A03, (2, 2)),
+    ];
+    for (variant, cycles) in expected {
+        for opcode in [0x69, 0xE9] {
+            assert_eq!(
+                decimal_cycles(variant, opcode),
+                cycles,
+                "{:?} {:02x}",
+                variant,
+                opcode
+            );
+        }
+    }
+}
+
+#[test]
+fn ricoh_ignores_decimal_flag() {
+    let cases = [
+        (0x69, 0x09, 0x01, false, 0x0A),
+        (0x69, 0x58, 0x46, true, 0x9F),
+        (0xE9, 0x10, 0x01, true, 0x0F),
+        (0xE9, 0x00, 0x01, true, 0xFF),
+    ];
+    for &(opcode, a, b, carry, expected) in cases.iter() {
+        let (result, _) = execute_on(CpuVariant::Ricoh2A03, opcode, a, b, carry);
+        assert_eq!(result, expected, "{:02x}: A={:02x} M={:02x}", opcode, a, b);
+    }
+}
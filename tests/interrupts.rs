@@ -0,0 +1,200 @@
+use volve::cpu::{Cpu, State};
+use volve::error::VolveError;
+use volve::memory::{IRQ, NMI, RESET};
+use volve::registers::StatusFlag;
+use volve::variant::CpuVariant;
+
+const BREAK: u8 = StatusFlag::Break as u8;
+const UNUSED: u8 = StatusFlag::Unused as u8;
+
+const IRQ_HANDLER: u16 = 0x0300;
+const NMI_HANDLER: u16 = 0x0400;
+
+/// A CPU with `code` at $0200, NOPs in both handlers and the stack empty.
+fn setup(variant: CpuVariant, code: &[u8]) -> Cpu {
+    let mut cpu = Cpu::with_variant(variant);
+    for (offset, &byte) in code.iter().enumerate() {
+        cpu.bus.write_byte(0x0200 + offset as u16, byte);
+    }
+    cpu.bus.write_byte(IRQ_HANDLER, 0xEA);
+    cpu.bus.write_byte(NMI_HANDLER, 0xEA);
+    cpu.bus.write_word(IRQ, IRQ_HANDLER);
+    cpu.bus.write_word(NMI, NMI_HANDLER);
+    cpu.registers.pc = 0x0200;
+    cpu.registers.sp = 0xFF;
+    cpu
+}
+
+/// The return address and P an interrupt left on a previously empty stack.
+fn pushed(cpu: &Cpu) -> (u16, u8) {
+    assert_eq!(cpu.registers.sp, 0xFC);
+    (cpu.bus.read_word(0x01FE), cpu.bus.read_byte(0x01FD))
+}
+
+#[test]
+fn irq_is_masked_by_i() {
+    // NOP, NOP
+    let mut cpu = setup(CpuVariant::Wdc65C02, &[0xEA, 0xEA]);
+    cpu.registers.set_flag(StatusFlag::NoInterrupts, true);
+    cpu.assert_irq();
+
+    assert_eq!(cpu.step().unwrap(), 2);
+    assert_eq!(cpu.registers.pc, 0x0201);
+
+    cpu.registers.set_flag(StatusFlag::NoInterrupts, false);
+    assert_eq!(cpu.step().unwrap(), 7);
+    assert_eq!(cpu.registers.pc, IRQ_HANDLER);
+    assert!(cpu.registers.get_flag(StatusFlag::NoInterrupts));
+
+    let (address, p) = pushed(&cpu);
+    assert_eq!(address, 0x0201);
+    assert_eq!(p & (BREAK | UNUSED), UNUSED);
+}
+
+#[test]
+fn nmi_is_latched_once_per_edge() {
+    let mut cpu = setup(CpuVariant::Wdc65C02, &[0xEA]);
+    // I does not mask NMI, and two triggers before the CPU gets to it are
+    // still a single edge
+    cpu.registers.set_flag(StatusFlag::NoInterrupts, true);
+    cpu.trigger_nmi();
+    cpu.trigger_nmi();
+
+    assert_eq!(cpu.step().unwrap(), 7);
+    assert_eq!(cpu.registers.pc, NMI_HANDLER);
+    assert_eq!(pushed(&cpu).0, 0x0200);
+
+    // the handler runs instead of taking the NMI again
+    assert_eq!(cpu.step().unwrap(), 2);
+    assert_eq!(cpu.registers.pc, NMI_HANDLER + 1);
+}
+
+#[test]
+fn nmi_takes_priority_over_irq() {
+    let mut cpu = setup(CpuVariant::Wdc65C02, &[0xEA]);
+    cpu.registers.set_flag(StatusFlag::NoInterrupts, false);
+    cpu.assert_irq();
+    cpu.trigger_nmi();
+
+    cpu.step().unwrap();
+    assert_eq!(cpu.registers.pc, NMI_HANDLER);
+}
+
+#[test]
+fn brk_pushes_b() {
+    // BRK and its signature byte
+    let mut cpu = setup(CpuVariant::Wdc65C02, &[0x00, 0xFF]);
+
+    assert_eq!(cpu.step().unwrap(), 7);
+    assert_eq!(cpu.registers.pc, IRQ_HANDLER);
+    assert!(cpu.registers.get_flag(StatusFlag::NoInterrupts));
+
+    let (address, p) = pushed(&cpu);
+    assert_eq!(address, 0x0202);
+    assert_eq!(p & (BREAK | UNUSED), BREAK | UNUSED);
+}
+
+#[test]
+fn rti_restores_flags_and_pc() {
+    // RTI
+    let mut cpu = setup(CpuVariant::Wdc65C02, &[0x40]);
+    cpu.registers.p = StatusFlag::NoInterrupts as u8 | UNUSED;
+    cpu.registers.sp = 0xFC;
+    // N, V, D, I and C set, B too, which RTI ignores
+    cpu.bus.write_byte(0x01FD, 0xDD);
+    cpu.bus.write_word(0x01FE, 0x1234);
+
+    assert_eq!(cpu.step().unwrap(), 6);
+    assert_eq!(cpu.registers.pc, 0x1234);
+    assert_eq!(cpu.registers.sp, 0xFF);
+    assert_eq!(cpu.registers.p, 0xCD | UNUSED);
+}
+
+#[test]
+fn cmos_clears_decimal_on_interrupt() {
+    let expected = [
+        (CpuVariant::Wdc65C02, false),
+        (CpuVariant::Rockwell65C02, false),
+        (CpuVariant::Nmos6502, true),
+    ];
+
+    for (variant, decimal) in expected {
+        // BRK
+        let mut cpu = setup(variant, &[0x00, 0x00]);
+        cpu.registers.set_flag(StatusFlag::Decimal, true);
+        cpu.step().unwrap();
+        assert_eq!(
+            cpu.registers.get_flag(StatusFlag::Decimal),
+            decimal,
+            "{:?} BRK",
+            variant
+        );
+
+        // IRQ
+        let mut cpu = setup(variant, &[0xEA]);
+        cpu.registers.set_flag(StatusFlag::Decimal, true);
+        cpu.registers.set_flag(StatusFlag::NoInterrupts, false);
+        cpu.assert_irq();
+        cpu.step().unwrap();
+        assert_eq!(
+            cpu.registers.get_flag(StatusFlag::Decimal),
+            decimal,
+            "{:?} IRQ",
+            variant
+        );
+
+        // the pushed copy keeps D, so RTI brings it back
+        let (_, p) = pushed(&cpu);
+        assert_ne!(p & StatusFlag::Decimal as u8, 0);
+    }
+}
+
+#[test]
+fn wai_resumes_on_irq_even_with_i_set() {
+    // WAI, NOP
+    let mut cpu = setup(CpuVariant::Wdc65C02, &[0xCB, 0xEA]);
+    cpu.registers.set_flag(StatusFlag::NoInterrupts, true);
+
+    cpu.step().unwrap();
+    assert_eq!(cpu.state(), State::Waiting);
+    assert_eq!(cpu.step().unwrap(), 1);
+    assert_eq!(cpu.state(), State::Waiting);
+
+    // with I set, execution simply continues after the WAI
+    cpu.assert_irq();
+    assert_eq!(cpu.step().unwrap(), 2);
+    assert_eq!(cpu.state(), State::Running);
+    assert_eq!(cpu.registers.pc, 0x0202);
+}
+
+#[test]
+fn wai_takes_the_interrupt_with_i_clear() {
+    let mut cpu = setup(CpuVariant::Wdc65C02, &[0xCB, 0xEA]);
+    cpu.registers.set_flag(StatusFlag::NoInterrupts, false);
+
+    cpu.step().unwrap();
+    cpu.assert_irq();
+    assert_eq!(cpu.step().unwrap(), 7);
+    assert_eq!(cpu.registers.pc, IRQ_HANDLER);
+    assert_eq!(pushed(&cpu).0, 0x0201);
+}
+
+#[test]
+fn stp_halts_until_reset() {
+    // STP
+    let mut cpu = setup(CpuVariant::Wdc65C02, &[0xDB]);
+    cpu.bus.write_word(RESET, 0x0200);
+
+    assert!(matches!(cpu.run(), Err(VolveError::Halted)));
+    assert_eq!(cpu.state(), State::Stopped);
+
+    // interrupts do not wake it up, it only idles
+    cpu.trigger_nmi();
+    cpu.assert_irq();
+    assert_eq!(cpu.step().unwrap(), 1);
+    assert_eq!(cpu.registers.pc, 0x0201);
+
+    cpu.reset();
+    assert_eq!(cpu.state(), State::Running);
+    assert_eq!(cpu.registers.pc, 0x0200);
+}
@@ -0,0 +1,126 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use volve::bus::Bus;
+use volve::devices::lcd::{Hd44780, Wiring};
+use volve::devices::via::{self, Via};
+use volve::memory::Memory;
+
+const BASE: u16 = 0x6000;
+
+// control lines of the 8-bit wiring on port A
+const RS: u8 = 0x20;
+const RW: u8 = 0x40;
+const E: u8 = 0x80;
+
+// the 4-bit wiring has the control lines on port B, above DB4-DB7
+const RS_4: u8 = 0x10;
+const E_4: u8 = 0x40;
+
+/// An LCD wired to a VIA on a bare bus, which stands in for the CPU.
+fn setup(wiring: Wiring) -> (Memory, Rc<RefCell<Hd44780>>) {
+    let lcd = Rc::new(RefCell::new(Hd44780::new(16, 2)));
+    let mut via = Via::new();
+    Hd44780::connect(&lcd, &mut via, wiring);
+
+    let mut memory = Memory::new();
+    memory.attach(BASE..=BASE + 0x0F, via);
+    (memory, lcd)
+}
+
+fn write(memory: &mut Memory, register: u16, value: u8) {
+    memory.write(BASE + register, value);
+}
+
+/// Writes `value` with the 8-bit wiring, `rs` selecting the data register.
+fn send(memory: &mut Memory, rs: bool, value: u8) {
+    let rs = if rs { RS } else { 0 };
+    write(memory, via::ORB, value);
+    write(memory, via::ORA, rs);
+    write(memory, via::ORA, rs | E);
+    write(memory, via::ORA, rs);
+}
+
+/// Writes `nibble` with the 4-bit wiring.
+fn send_nibble(memory: &mut Memory, rs: bool, nibble: u8) {
+    let rs = if rs { RS_4 } else { 0 };
+    write(memory, via::ORB, rs | nibble);
+    write(memory, via::ORB, rs | nibble | E_4);
+    write(memory, via::ORB, rs | nibble);
+}
+
+fn send_4(memory: &mut Memory, rs: bool, value: u8) {
+    send_nibble(memory, rs, value >> 4);
+    send_nibble(memory, rs, value & 0x0F);
+}
+
+/// Reads the busy flag and address counter with the 8-bit wiring.
+fn status(memory: &mut Memory) -> u8 {
+    write(memory, via::ORA, RW);
+    write(memory, via::ORA, RW | E);
+    let value = memory.read(BASE + via::ORB);
+    write(memory, via::ORA, RW);
+    value
+}
+
+#[test]
+fn eight_bit_interface() {
+    let (mut memory, lcd) = setup(Wiring::eight_bit());
+    write(&mut memory, via::DDRB, 0xFF);
+    write(&mut memory, via::DDRA, RS | RW | E);
+
+    // 8-bit, two lines, display on, increment
+    for instruction in [0x38, 0x0C, 0x06, 0x01] {
+        send(&mut memory, false, instruction);
+    }
+    for &byte in b"Hello" {
+        send(&mut memory, true, byte);
+    }
+    send(&mut memory, false, 0xC0);
+    for &byte in b"World" {
+        send(&mut memory, true, byte);
+    }
+
+    assert_eq!(lcd.borrow().text(), "Hello           \nWorld           ");
+}
+
+#[test]
+fn eight_bit_busy_flag_and_address() {
+    let (mut memory, lcd) = setup(Wiring::eight_bit());
+    write(&mut memory, via::DDRB, 0xFF);
+    write(&mut memory, via::DDRA, RS | RW | E);
+    memory.sync(100);
+    send(&mut memory, false, 0x38);
+    send(&mut memory, true, b'A');
+
+    // port B is turned around to read the status while E is high
+    write(&mut memory, via::DDRB, 0x00);
+    assert_eq!(status(&mut memory), 0x80 | 0x01);
+    assert!(lcd.borrow().is_busy(100));
+
+    // a write takes 37 microseconds, at the default clock of 1 MHz
+    memory.sync(100 + 37);
+    assert_eq!(status(&mut memory), 0x01);
+}
+
+#[test]
+fn four_bit_interface() {
+    let (mut memory, lcd) = setup(Wiring::four_bit());
+    write(&mut memory, via::DDRB, 0x7F);
+
+    // the first function set is a single nibble, as the LCD still
+    // expects 8 bits
+    send_nibble(&mut memory, false, 0x02);
+    for instruction in [0x28, 0x0C, 0x06, 0x01] {
+        send_4(&mut memory, false, instruction);
+    }
+    for &byte in b"4-bit" {
+        send_4(&mut memory, true, byte);
+    }
+    send_4(&mut memory, false, 0xC0 | 0x03);
+    for &byte in b"ok" {
+        send_4(&mut memory, true, byte);
+    }
+
+    assert_eq!(lcd.borrow().text(), "4-bit           \n   ok           ");
+    assert_eq!(lcd.borrow().cursor(), (5, 1));
+}
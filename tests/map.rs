@@ -0,0 +1,226 @@
+use std::ops::RangeInclusive;
+use volve::bus::Bus;
+use volve::cpu::Cpu;
+use volve::device::{Context, Device};
+use volve::error::VolveError;
+use volve::map::{MemoryMap, MemoryMapBuilder, RomWrite, RomWritePolicy};
+use volve::variant::CpuVariant;
+
+/// A device that reads back the last value written to any of its addresses.
+struct Latch(u8);
+
+impl Device for Latch {
+    fn read(&mut self, offset: u16, _context: &mut Context) -> u8 {
+        self.peek(offset)
+    }
+
+    fn write(&mut self, _offset: u16, value: u8, _context: &mut Context) {
+        self.0 = value;
+    }
+
+    fn peek(&self, _offset: u16) -> u8 {
+        self.0
+    }
+}
+
+/// 32K of RAM and 32K of ROM counting up from $00.
+fn board() -> MemoryMapBuilder {
+    let image: Vec<u8> = (0..=0xFF).collect();
+    MemoryMap::builder()
+        .ram(0x0000..=0x7FFF)
+        .and_then(|builder| builder.rom(0x8000..=0xFFFF, &image))
+        .unwrap()
+}
+
+#[test]
+fn later_regions_take_precedence() {
+    let mut map = board()
+        .device(0x6000..=0x600F, Latch(0x42))
+        .unwrap()
+        .build();
+
+    map.write(0x5FFF, 0x11);
+    map.write(0x6010, 0x22);
+    assert_eq!(map.read(0x5FFF), 0x11);
+    assert_eq!(map.read(0x6010), 0x22);
+
+    // the device covers the RAM underneath it
+    assert_eq!(map.read(0x6005), 0x42);
+    map.write(0x6005, 0x33);
+    assert_eq!(map.read(0x600F), 0x33);
+    assert_eq!(map.peek(0x6000), 0x33);
+}
+
+#[test]
+fn mirrors_repeat_their_target() {
+    // 2K of RAM decoded over 8K
+    let mut map = MemoryMap::builder()
+        .ram(0x0000..=0x07FF)
+        .and_then(|builder| builder.mirror(0x0800..=0x1FFF, 0x0000..=0x07FF))
+        .unwrap()
+        .build();
+
+    map.write(0x0123, 0x42);
+    for copy in 1..4 {
+        assert_eq!(map.read(copy * 0x0800 + 0x0123), 0x42);
+    }
+    map.write(0x1923, 0x24);
+    assert_eq!(map.read(0x0123), 0x24);
+}
+
+#[test]
+fn mirrors_follow_chains_but_not_cycles() {
+    let mut map = MemoryMap::builder()
+        .ram(0x0000..=0x00FF)
+        .and_then(|builder| builder.mirror(0x0100..=0x01FF, 0x0000..=0x00FF))
+        .and_then(|builder| builder.mirror(0x0200..=0x02FF, 0x0100..=0x01FF))
+        // two mirrors of each other
+        .and_then(|builder| builder.mirror(0x1000..=0x10FF, 0x2000..=0x20FF))
+        .and_then(|builder| builder.mirror(0x2000..=0x20FF, 0x1000..=0x10FF))
+        .map(|builder| builder.open_bus(0xEA))
+        .unwrap()
+        .build();
+
+    map.write(0x0010, 0x42);
+    assert_eq!(map.read(0x0210), 0x42);
+
+    // a cycle reads as open bus instead of hanging
+    assert_eq!(map.read(0x1010), 0xEA);
+    map.write(0x1010, 0x42);
+    assert_eq!(map.peek(0x2010), 0xEA);
+}
+
+#[test]
+fn unmapped_addresses_read_open_bus() {
+    let mut map = board()
+        .unmapped(0x4000..=0x4FFF)
+        .map(|builder| builder.open_bus(0xEA))
+        .unwrap()
+        .build();
+
+    // a hole cut into the RAM drops writes
+    map.write(0x4000, 0x42);
+    assert_eq!(map.read(0x4000), 0xEA);
+    assert_eq!(map.peek(0x4FFF), 0xEA);
+    assert_eq!(map.read(0x3FFF), 0x00);
+
+    // as do addresses outside of every region, $FF unless set
+    let mut map = MemoryMap::builder().ram(0x0000..=0x00FF).unwrap().build();
+    map.write(0x0100, 0x42);
+    assert_eq!(map.read(0x0100), 0xFF);
+}
+
+#[test]
+fn rom_writes_are_ignored_by_default() {
+    let mut map = board().build();
+
+    map.write(0x8001, 0x42);
+    assert_eq!(map.read(0x8001), 0x01);
+    assert_eq!(map.take_rom_writes(), []);
+    assert_eq!(map.take_fault(), None);
+}
+
+#[test]
+fn rom_writes_can_be_reported() {
+    let mut map = board().rom_write_policy(RomWritePolicy::Report).build();
+
+    map.write(0x8001, 0x42);
+    map.write(0x8002, 0x24);
+    assert_eq!(map.read(0x8001), 0x01);
+    assert_eq!(
+        map.take_rom_writes(),
+        [
+            RomWrite {
+                address: 0x8001,
+                value: 0x42
+            },
+            RomWrite {
+                address: 0x8002,
+                value: 0x24
+            },
+        ]
+    );
+    assert_eq!(map.take_rom_writes(), []);
+    assert_eq!(map.take_fault(), None);
+}
+
+#[test]
+fn rom_writes_can_fault() {
+    let mut map = board().rom_write_policy(RomWritePolicy::Fault).build();
+    // STA $8001
+    map.write(0x0200, 0x8D);
+    map.write(0x0201, 0x01);
+    map.write(0x0202, 0x80);
+
+    let mut cpu = Cpu::with_bus(map, CpuVariant::default());
+    cpu.registers.pc = 0x0200;
+    match cpu.step() {
+        Err(VolveError::BusFault { address }) => assert_eq!(address, 0x8001),
+        result => panic!("expected a bus fault, got {:?}", result),
+    }
+    assert_eq!(cpu.bus.read(0x8001), 0x01);
+}
+
+#[test]
+fn invalid_regions_are_rejected() {
+    let errors = [
+        // an empty range
+        MemoryMap::builder()
+            .ram(RangeInclusive::new(0x2000, 0x1FFF))
+            .err(),
+        // a mirror overlapping its target
+        MemoryMap::builder()
+            .mirror(0x0000..=0x1FFF, 0x0800..=0x0FFF)
+            .err(),
+        // banked regions without banks
+        MemoryMap::builder()
+            .banked_ram(0x8000..=0xBFFF, 0x0300, 0)
+            .err(),
+        MemoryMap::builder()
+            .banked_rom(0x8000..=0xBFFF, 0x0300, &[])
+            .err(),
+    ];
+
+    for error in errors {
+        assert!(
+            matches!(error, Some(VolveError::InvalidRegion { .. })),
+            "{:?}",
+            error
+        );
+    }
+}
+
+#[test]
+fn bank_latch_in_rom_is_exempt_from_the_policy() {
+    // two 16K banks at $8000, selected through a latch at $FFF0 in the
+    // fixed ROM above them
+    let banks = [vec![0xA0; 0x4000], vec![0xA1; 0x4000]];
+    let mut map = MemoryMap::builder()
+        .ram(0x0000..=0x7FFF)
+        .and_then(|builder| builder.rom(0xC000..=0xFFFF, &[]))
+        .and_then(|builder| builder.banked_rom(0x8000..=0xBFFF, 0xFFF0, &banks))
+        .unwrap()
+        .rom_write_policy(RomWritePolicy::Fault)
+        .build();
+    // LDA #$01, STA $FFF0, LDA $8000, STA $FFF1
+    let code = [
+        0xA9, 0x01, 0x8D, 0xF0, 0xFF, 0xAD, 0x00, 0x80, 0x8D, 0xF1, 0xFF,
+    ];
+    for (offset, &byte) in code.iter().enumerate() {
+        map.write(0x0200 + offset as u16, byte);
+    }
+
+    let mut cpu = Cpu::with_bus(map, CpuVariant::default());
+    cpu.registers.pc = 0x0200;
+    for _ in 0..3 {
+        cpu.step().unwrap();
+    }
+    assert_eq!(cpu.registers.a, 0xA1);
+    assert_eq!(cpu.bus.bank(0x8000), Some(1));
+
+    // any other write to the ROM still faults
+    match cpu.step() {
+        Err(VolveError::BusFault { address }) => assert_eq!(address, 0xFFF1),
+        result => panic!("expected a bus fault, got {:?}", result),
+    }
+}
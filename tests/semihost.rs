@@ -0,0 +1,147 @@
+use std::cell::RefCell;
+use std::io::{self, Write};
+use std::rc::Rc;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use volve::bus::Bus;
+use volve::cpu::Cpu;
+use volve::devices::semihost::{self, Semihost, Status};
+use volve::memory::{Memory, RESET};
+use volve::variant::CpuVariant;
+
+const BASE: u16 = 0xF000;
+
+/// An output stream the test can look at once the device has it.
+#[derive(Clone, Default)]
+struct Sink(Rc<RefCell<Vec<u8>>>);
+
+impl Write for Sink {
+    fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+        self.0.borrow_mut().extend_from_slice(bytes);
+        Ok(bytes.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// A CPU that runs `code` from $0200, with the semihost at `BASE` reading
+/// `input` and writing to the sinks returned alongside.
+fn setup(code: &[u8], input: &'static [u8]) -> (Cpu, Sink, Sink) {
+    let stdout = Sink::default();
+    let stderr = Sink::default();
+    let mut memory = Memory::new();
+    memory.attach(
+        BASE..=BASE + semihost::SIZE - 1,
+        Semihost::with_streams(input, stdout.clone(), stderr.clone()),
+    );
+    for (offset, &byte) in code.iter().enumerate() {
+        memory.write_byte(0x0200 + offset as u16, byte);
+    }
+    memory.write_word(RESET, 0x0200);
+
+    (Cpu::with_bus(memory, CpuVariant::default()), stdout, stderr)
+}
+
+fn little_endian(bytes: &[u8]) -> u64 {
+    bytes
+        .iter()
+        .rev()
+        .fold(0, |value, &byte| (value << 8) | byte as u64)
+}
+
+#[test]
+fn exit_ends_the_run_with_its_status() {
+    // LDA #$2A, STA EXIT, then a loop the CPU must never get to
+    let (mut cpu, _, _) = setup(&[0xA9, 0x2A, 0x8D, 0x04, 0xF0, 0x4C, 0x05, 0x02], b"");
+
+    cpu.run().unwrap();
+    assert_eq!(cpu.bus.exit_code(), Some(0x2A));
+    assert_eq!(cpu.registers.pc, 0x0205);
+}
+
+#[test]
+fn streams_reach_the_host() {
+    let code = [
+        // LDX #0, copy stdin to stdout until it runs dry
+        0xA2, 0x00, // $0200
+        0xAD, 0x02, 0xF0, // $0202 LDA STDIN
+        0xAC, 0x03, 0xF0, // LDY STATUS
+        0xD0, 0x05, // BNE done
+        0x8D, 0x00, 0xF0, // STA STDOUT
+        0x80, 0xF3, // BRA $0202
+        // done: "!" to stderr, then exit
+        0xA9, b'!', 0x8D, 0x01, 0xF0, // $020F
+        0x8C, 0x04, 0xF0, // STY EXIT
+    ];
+    let (mut cpu, stdout, stderr) = setup(&code, b"Hi\n");
+
+    cpu.run().unwrap();
+    assert_eq!(*stdout.0.borrow(), b"Hi\n");
+    assert_eq!(*stderr.0.borrow(), b"!");
+    assert_eq!(cpu.bus.exit_code(), Some(Status::EndOfInput as u8));
+}
+
+#[test]
+fn cycles_latch_on_their_low_byte() {
+    let mut code = vec![
+        0xEA, // NOP, for the devices to catch up with the reset
+        0xAD, 0x10, 0xF0, // LDA CYCLES
+        0x85, 0x10, // STA $10
+        // count X down from 256, so the counter moves past the latched
+        // value by more than a byte's worth
+        0xA2, 0x00, // LDX #0
+        0xCA, 0xD0, 0xFD, // DEX, BNE
+    ];
+    for offset in 1..8 {
+        // LDA CYCLES+offset, STA $10+offset
+        code.extend([0xAD, 0x10 + offset, 0xF0, 0x85, 0x10 + offset]);
+    }
+    code.extend([0xA9, 0x00, 0x8D, 0x04, 0xF0]);
+    let (mut cpu, _, _) = setup(&code, b"");
+
+    cpu.reset();
+    cpu.step().unwrap();
+    let latched = cpu.cycles();
+    while cpu.bus.exit_code().is_none() {
+        cpu.step().unwrap();
+    }
+    assert!(cpu.cycles() > latched + 0xFF);
+
+    let bytes: Vec<u8> = (0x10..0x18).map(|address| cpu.bus.peek(address)).collect();
+    assert_eq!(little_endian(&bytes), latched);
+}
+
+#[test]
+fn time_latches_on_its_low_byte() {
+    let mut memory = Memory::new();
+    memory.attach(BASE..=BASE + semihost::SIZE - 1, Semihost::new());
+    let now = || {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64
+    };
+    let time = |memory: &Memory| {
+        let bytes: Vec<u8> = (0..8)
+            .map(|offset| memory.peek(BASE + semihost::TIME + offset))
+            .collect();
+        little_endian(&bytes)
+    };
+
+    let before = now();
+    memory.read(BASE + semihost::TIME);
+    let after = now();
+    let latched = time(&memory);
+    assert!((before..=after).contains(&latched));
+
+    // the upper bytes hold still until the low byte is read again
+    thread::sleep(Duration::from_millis(5));
+    for offset in 1..8 {
+        memory.read(BASE + semihost::TIME + offset);
+    }
+    assert_eq!(time(&memory), latched);
+    memory.read(BASE + semihost::TIME);
+    assert!(time(&memory) > latched);
+}
@@ -0,0 +1,229 @@
+use volve::cpu::{Cpu, State};
+use volve::registers::StatusFlag;
+use volve::variant::CpuVariant;
+
+const CARRY: u8 = StatusFlag::Carry as u8;
+const ZERO: u8 = StatusFlag::Zero as u8;
+const NEGATIVE: u8 = StatusFlag::Negative as u8;
+
+/// An NMOS 6502 with `code` at $0200, ready to execute it.
+fn setup(code: &[u8]) -> Cpu {
+    setup_on(CpuVariant::Nmos6502, code)
+}
+
+fn setup_on(variant: CpuVariant, code: &[u8]) -> Cpu {
+    let mut cpu = Cpu::with_variant(variant);
+    for (offset, &byte) in code.iter().enumerate() {
+        cpu.bus.write_byte(0x0200 + offset as u16, byte);
+    }
+    cpu.registers.pc = 0x0200;
+    cpu.registers.p = StatusFlag::Unused as u8;
+    cpu
+}
+
+fn flags(cpu: &Cpu) -> u8 {
+    cpu.registers.p & (NEGATIVE | ZERO | CARRY)
+}
+
+#[test]
+fn lax_loads_a_and_x() {
+    // LAX $10
+    let mut cpu = setup(&[0xA7, 0x10]);
+    cpu.bus.write_byte(0x0010, 0x80);
+
+    assert_eq!(cpu.step().unwrap(), 3);
+    assert_eq!((cpu.registers.a, cpu.registers.x), (0x80, 0x80));
+    assert_eq!(flags(&cpu), NEGATIVE);
+}
+
+#[test]
+fn sax_stores_a_and_x() {
+    // SAX $10
+    let mut cpu = setup(&[0x87, 0x10]);
+    cpu.registers.a = 0xF0;
+    cpu.registers.x = 0x3C;
+
+    assert_eq!(cpu.step().unwrap(), 3);
+    assert_eq!(cpu.bus.read_byte(0x0010), 0x30);
+    // unlike a load, the store leaves the flags alone
+    assert_eq!(flags(&cpu), 0);
+}
+
+/// Runs the read-modify-write `opcode $10` with `memory` at $10 and returns
+/// the new memory, A and flags.
+fn modify(opcode: u8, memory: u8, a: u8, carry: bool) -> (u8, u8, u8) {
+    let mut cpu = setup(&[opcode, 0x10]);
+    cpu.bus.write_byte(0x0010, memory);
+    cpu.registers.a = a;
+    cpu.registers.set_flag(StatusFlag::Carry, carry);
+
+    assert_eq!(cpu.step().unwrap(), 5, "{:02X}", opcode);
+    (cpu.bus.read_byte(0x0010), cpu.registers.a, flags(&cpu))
+}
+
+#[test]
+fn combined_read_modify_write() {
+    // DCP: DEC, then CMP
+    assert_eq!(modify(0xC7, 0x11, 0x10, false), (0x10, 0x10, ZERO | CARRY));
+    // ISC: INC, then SBC
+    assert_eq!(modify(0xE7, 0x0F, 0x20, true), (0x10, 0x10, CARRY));
+    // SLO: ASL, then ORA
+    assert_eq!(modify(0x07, 0x81, 0x01, false), (0x02, 0x03, CARRY));
+    // RLA: ROL, then AND
+    assert_eq!(modify(0x27, 0x81, 0xFF, false), (0x02, 0x02, CARRY));
+    // SRE: LSR, then EOR
+    assert_eq!(
+        modify(0x47, 0x81, 0xFF, false),
+        (0x40, 0xBF, NEGATIVE | CARRY)
+    );
+    // RRA: ROR, then ADC with the carry ROR shifted out
+    assert_eq!(modify(0x67, 0x02, 0x10, true), (0x81, 0x91, NEGATIVE));
+}
+
+#[test]
+fn ane_and_lxa_use_the_magic_constant() {
+    for (magic, expected) in [(0xEE, 0x0E), (0xFF, 0x0F), (0x00, 0x00)] {
+        // ANE #$0F: A = (A | magic) & X & operand
+        let mut cpu = setup(&[0x8B, 0x0F]);
+        cpu.set_magic(magic);
+        cpu.registers.x = 0xFF;
+        cpu.step().unwrap();
+        assert_eq!(cpu.registers.a, expected, "ANE magic {:02X}", magic);
+
+        // LXA #$0F: A = X = (A | magic) & operand
+        let mut cpu = setup(&[0xAB, 0x0F]);
+        cpu.set_magic(magic);
+        cpu.step().unwrap();
+        assert_eq!(
+            (cpu.registers.a, cpu.registers.x),
+            (expected, expected),
+            "LXA magic {:02X}",
+            magic
+        );
+    }
+}
+
+#[test]
+fn unstable_stores_and_with_the_high_byte() {
+    // SHA $1200,Y stores A & X & $13
+    let mut cpu = setup(&[0x9F, 0x00, 0x12]);
+    cpu.registers.a = 0xFF;
+    cpu.registers.x = 0x0F;
+    cpu.step().unwrap();
+    assert_eq!(cpu.bus.read_byte(0x1200), 0x03);
+
+    // SHX $1200,Y stores X & $13
+    let mut cpu = setup(&[0x9E, 0x00, 0x12]);
+    cpu.registers.x = 0xFF;
+    cpu.registers.y = 0x05;
+    cpu.step().unwrap();
+    assert_eq!(cpu.bus.read_byte(0x1205), 0x13);
+
+    // SHY $1200,X stores Y & $13
+    let mut cpu = setup(&[0x9C, 0x00, 0x12]);
+    cpu.registers.y = 0x31;
+    cpu.registers.x = 0x05;
+    cpu.step().unwrap();
+    assert_eq!(cpu.bus.read_byte(0x1205), 0x11);
+
+    // TAS $1200,Y sets SP to A & X and stores SP & $13
+    let mut cpu = setup(&[0x9B, 0x00, 0x12]);
+    cpu.registers.a = 0xF3;
+    cpu.registers.x = 0x3F;
+    cpu.step().unwrap();
+    assert_eq!(cpu.registers.sp, 0x33);
+    assert_eq!(cpu.bus.read_byte(0x1200), 0x13);
+}
+
+#[test]
+fn unstable_store_across_a_page_replaces_the_high_byte() {
+    // SHX $12F0,Y with Y = $20 and X = $FF stores $13, which happens to
+    // leave the address at $1310
+    let mut cpu = setup(&[0x9E, 0xF0, 0x12]);
+    cpu.registers.x = 0xFF;
+    cpu.registers.y = 0x20;
+    cpu.step().unwrap();
+    assert_eq!(cpu.bus.read_byte(0x1310), 0x13);
+
+    // with X = $0F only $03 is left of the high byte
+    let mut cpu = setup(&[0x9E, 0xF0, 0x12]);
+    cpu.registers.x = 0x0F;
+    cpu.registers.y = 0x20;
+    cpu.step().unwrap();
+    assert_eq!(cpu.bus.read_byte(0x0310), 0x03);
+    assert_eq!(cpu.bus.read_byte(0x1310), 0x00);
+}
+
+#[test]
+fn jam_locks_up_the_cpu() {
+    for opcode in [
+        0x02, 0x12, 0x22, 0x32, 0x42, 0x52, 0x62, 0x72, 0x92, 0xB2, 0xD2, 0xF2,
+    ] {
+        let mut cpu = setup(&[opcode, 0xEA]);
+        cpu.step().unwrap();
+        assert_eq!(cpu.state(), State::Stopped, "{:02X}", opcode);
+
+        // the NOP behind it never runs, the CPU only idles
+        let pc = cpu.registers.pc;
+        assert_eq!(cpu.step().unwrap(), 1);
+        assert_eq!(cpu.registers.pc, pc, "{:02X}", opcode);
+    }
+}
+
+#[test]
+fn cmos_executes_unused_opcodes_as_nops() {
+    // (opcode, length, cycles) from the W65C02S datasheet
+    let mut nops = vec![
+        (0x44, 2, 3),
+        (0x54, 2, 4),
+        (0xD4, 2, 4),
+        (0xF4, 2, 4),
+        (0x5C, 3, 8),
+        (0xDC, 3, 4),
+        (0xFC, 3, 4),
+    ];
+    for opcode in [0x02, 0x22, 0x42, 0x62, 0x82, 0xC2, 0xE2] {
+        nops.push((opcode, 2, 2));
+    }
+    for row in 0..16 {
+        nops.push((row << 4 | 0x03, 1, 1));
+        if row != 0x0C && row != 0x0D {
+            nops.push((row << 4 | 0x0B, 1, 1));
+        }
+    }
+
+    for variant in [
+        CpuVariant::Wdc65C02,
+        CpuVariant::Rockwell65C02,
+        CpuVariant::Synertek65SC02,
+    ] {
+        for &(opcode, length, cycles) in &nops {
+            let mut cpu = setup_on(variant, &[opcode, 0xFF, 0xFF]);
+            cpu.registers.a = 0x12;
+            cpu.registers.x = 0x34;
+            cpu.registers.y = 0x56;
+            let p = cpu.registers.p;
+
+            assert_eq!(cpu.step().unwrap(), cycles, "{:?} {:02X}", variant, opcode);
+            assert_eq!(
+                cpu.registers.pc,
+                0x0200 + length,
+                "{:?} {:02X}",
+                variant,
+                opcode
+            );
+            assert_eq!(
+                (
+                    cpu.registers.a,
+                    cpu.registers.x,
+                    cpu.registers.y,
+                    cpu.registers.p
+                ),
+                (0x12, 0x34, 0x56, p),
+                "{:?} {:02X}",
+                variant,
+                opcode
+            );
+        }
+    }
+}
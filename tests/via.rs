@@ -0,0 +1,178 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use volve::bus::Bus;
+use volve::devices::via::{self, Interrupt, Port, Via};
+use volve::memory::Memory;
+
+const BASE: u16 = 0x6000;
+const T1: u8 = Interrupt::Timer1 as u8;
+const T2: u8 = Interrupt::Timer2 as u8;
+const CB1: u8 = Interrupt::Cb1 as u8;
+
+/// A VIA on a bare bus, which stands in for the CPU and its clock.
+fn setup() -> (Memory, Rc<RefCell<Via>>) {
+    let via = Rc::new(RefCell::new(Via::new()));
+    let mut memory = Memory::new();
+    memory.attach(BASE..=BASE + 0x0F, via.clone());
+    (memory, via)
+}
+
+fn write(memory: &mut Memory, register: u16, value: u8) {
+    memory.write(BASE + register, value);
+}
+
+fn read(memory: &mut Memory, register: u16) -> u8 {
+    memory.read(BASE + register)
+}
+
+fn ifr(memory: &Memory) -> u8 {
+    memory.peek(BASE + via::IFR)
+}
+
+#[test]
+fn t1_one_shot_fires_once() {
+    let (mut memory, via) = setup();
+    write(&mut memory, via::IER, 0x80 | T1);
+    write(&mut memory, via::T1C_L, 0x10);
+    write(&mut memory, via::T1C_H, 0x00);
+
+    // the counter runs down to zero and times out on the next cycle
+    memory.sync(0x10);
+    assert_eq!(ifr(&memory) & T1, 0);
+    assert!(!via.borrow().irq());
+    memory.sync(0x11);
+    assert_eq!(ifr(&memory), 0x80 | T1);
+    assert!(memory.irq());
+
+    read(&mut memory, via::T1C_L);
+    assert_eq!(ifr(&memory), 0);
+    assert!(!memory.irq());
+
+    // the counter keeps running, but does not interrupt again
+    memory.sync(0x30000);
+    assert_eq!(ifr(&memory), 0);
+}
+
+#[test]
+fn t1_free_running_reloads_from_latch() {
+    let (mut memory, _) = setup();
+    write(&mut memory, via::ACR, 0x40);
+    write(&mut memory, via::T1C_L, 0x10);
+    write(&mut memory, via::T1C_H, 0x00);
+
+    memory.sync(0x11);
+    assert_eq!(ifr(&memory) & T1, T1);
+    read(&mut memory, via::T1C_L);
+
+    // a period is the latch value plus two cycles
+    memory.sync(0x11 + 0x11);
+    assert_eq!(ifr(&memory) & T1, 0);
+    memory.sync(0x11 + 0x12);
+    assert_eq!(ifr(&memory) & T1, T1);
+}
+
+#[test]
+fn t2_one_shot_fires_once() {
+    let (mut memory, _) = setup();
+    write(&mut memory, via::T2C_L, 0x20);
+    write(&mut memory, via::T2C_H, 0x00);
+
+    memory.sync(0x20);
+    assert_eq!(ifr(&memory) & T2, 0);
+    memory.sync(0x21);
+    assert_eq!(ifr(&memory) & T2, T2);
+
+    read(&mut memory, via::T2C_L);
+    assert_eq!(ifr(&memory) & T2, 0);
+
+    // rolling over again does not set the flag without rewriting T2C_H
+    memory.sync(0x21 + 0x10000 + 0x10);
+    assert_eq!(ifr(&memory) & T2, 0);
+}
+
+#[test]
+fn ifr_bit_7_follows_ier() {
+    let (mut memory, via) = setup();
+    write(&mut memory, via::T1C_L, 0x01);
+    write(&mut memory, via::T1C_H, 0x00);
+    memory.sync(0x02);
+
+    // a flag that is not enabled does not pull IRQ
+    assert_eq!(ifr(&memory), T1);
+    assert!(!via.borrow().irq());
+
+    write(&mut memory, via::IER, 0x80 | T1);
+    assert_eq!(read(&mut memory, via::IER), 0x80 | T1);
+    assert_eq!(ifr(&memory), 0x80 | T1);
+    assert!(via.borrow().irq());
+
+    // writing with bit 7 clear disables the interrupt again
+    write(&mut memory, via::IER, T1);
+    assert_eq!(ifr(&memory), T1);
+    assert!(!via.borrow().irq());
+
+    // writing a 1 to a flag clears it
+    write(&mut memory, via::IER, 0x80 | T1);
+    write(&mut memory, via::IFR, T1);
+    assert_eq!(ifr(&memory), 0);
+    assert!(!via.borrow().irq());
+}
+
+/// Input levels driven onto a port from outside.
+struct Pins(u8);
+
+impl Port for Pins {
+    fn write(&mut self, _pins: u8, _ddr: u8, _cycles: u64) {}
+
+    fn read(&self, _cycles: u64) -> u8 {
+        self.0
+    }
+}
+
+#[test]
+fn peek_of_port_b_matches_read_without_side_effects() {
+    let (mut memory, via) = setup();
+    via.borrow_mut().connect_port_b(Pins(0xA0));
+    write(&mut memory, via::DDRB, 0x0F);
+    write(&mut memory, via::ORB, 0x05);
+    // T1 in one-shot mode on PB7, which goes low until it times out
+    write(&mut memory, via::ACR, 0x80);
+    write(&mut memory, via::T1C_L, 0x10);
+    write(&mut memory, via::T1C_H, 0x00);
+    // a CB1 edge, which reading ORB acknowledges
+    via.borrow_mut().set_cb1(false);
+    assert_eq!(ifr(&memory) & CB1, CB1);
+
+    assert_eq!(memory.peek(BASE + via::ORB), 0x25);
+    assert_eq!(ifr(&memory) & CB1, CB1);
+    assert_eq!(read(&mut memory, via::ORB), 0x25);
+    assert_eq!(ifr(&memory) & CB1, 0);
+
+    memory.sync(0x11);
+    assert_eq!(memory.peek(BASE + via::ORB), 0xA5);
+    assert_eq!(read(&mut memory, via::ORB), 0xA5);
+}
+
+#[test]
+fn cb1_is_the_shift_clock_in_internally_clocked_modes() {
+    let (mut memory, via) = setup();
+    via.borrow_mut().set_cb1(false);
+
+    // (ACR, whether CB1 is the external input): the modes clocked by CB1
+    // are 011 and 111, free-running 100 clocks from T2
+    let modes = [
+        (0x00, true),
+        (0x04, false),
+        (0x08, false),
+        (0x0C, true),
+        (0x10, false),
+        (0x14, false),
+        (0x18, false),
+        (0x1C, true),
+    ];
+    for (acr, external) in modes {
+        write(&mut memory, via::ACR, acr);
+        // the internal clock idles high, the external one is held low
+        assert_eq!(via.borrow().cb1(), !external, "ACR {:02X}", acr);
+    }
+}
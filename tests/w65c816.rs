@@ -0,0 +1,319 @@
+use volve::cpu::State;
+use volve::error::VolveError;
+use volve::w65c816::cpu::Cpu;
+use volve::w65c816::memory::{
+    BRK_NATIVE, COP, COP_NATIVE, IRQ, IRQ_NATIVE, NMI, NMI_NATIVE, RESET,
+};
+use volve::w65c816::registers::StatusFlag;
+
+const CARRY: u8 = StatusFlag::Carry as u8;
+const DECIMAL: u8 = StatusFlag::Decimal as u8;
+const INDEX: u8 = StatusFlag::IndexRegisters as u8;
+const ACCUMULATOR: u8 = StatusFlag::Accumulator as u8;
+const NEGATIVE: u8 = StatusFlag::Negative as u8;
+
+/// A CPU in emulation mode with `code` at $00:0200 and the stack empty.
+fn setup(code: &[u8]) -> Cpu {
+    let mut cpu = Cpu::new();
+    for (offset, &byte) in code.iter().enumerate() {
+        cpu.memory.write_byte(0x0200 + offset as u32, byte);
+    }
+    cpu.registers.pc = 0x0200;
+    cpu
+}
+
+/// The same in native mode, with 16-bit registers all around.
+fn setup_native(code: &[u8]) -> Cpu {
+    let mut cpu = setup(code);
+    cpu.registers.set_emulation(false);
+    cpu.registers
+        .set_status(cpu.registers.p & !(ACCUMULATOR | INDEX));
+    cpu
+}
+
+/// The bytes below the initial stack pointer, most recently pushed first.
+fn stack(cpu: &Cpu) -> Vec<u8> {
+    (cpu.registers.sp + 1..=0x01FF)
+        .map(|address| cpu.memory.read_byte(address as u32))
+        .collect()
+}
+
+#[test]
+fn xce_switches_modes() {
+    // CLC, XCE, REP #$30, SEC, XCE
+    let mut cpu = setup(&[0x18, 0xFB, 0xC2, 0x30, 0x38, 0xFB]);
+    cpu.step().unwrap();
+    cpu.step().unwrap();
+    assert!(!cpu.registers.emulation);
+    // C takes the old E
+    assert!(cpu.registers.get_flag(StatusFlag::Carry));
+    // M and X stay set until REP clears them
+    assert_eq!(cpu.registers.p & (ACCUMULATOR | INDEX), ACCUMULATOR | INDEX);
+
+    cpu.step().unwrap();
+    assert_eq!(cpu.registers.p & (ACCUMULATOR | INDEX), 0);
+    cpu.registers.x = 0x1234;
+    cpu.registers.sp = 0x2345;
+
+    cpu.step().unwrap();
+    cpu.step().unwrap();
+    assert!(cpu.registers.emulation);
+    assert!(!cpu.registers.get_flag(StatusFlag::Carry));
+    // emulation mode forces 8-bit registers and the stack into page $01
+    assert_eq!(cpu.registers.p & (ACCUMULATOR | INDEX), ACCUMULATOR | INDEX);
+    assert_eq!(cpu.registers.x, 0x0034);
+    assert_eq!(cpu.registers.sp, 0x0145);
+}
+
+#[test]
+fn rep_and_sep_change_operand_widths() {
+    // LDA #$1234, SEP #$20, LDA #$56, LDX #$789A, SEP #$10, LDX #$BC
+    let mut cpu = setup_native(&[
+        0xA9, 0x34, 0x12, 0xE2, 0x20, 0xA9, 0x56, 0xA2, 0x9A, 0x78, 0xE2, 0x10, 0xA2, 0xBC,
+    ]);
+
+    cpu.step().unwrap();
+    assert_eq!(cpu.registers.a, 0x1234);
+    assert_eq!(cpu.registers.pc, 0x0203);
+
+    cpu.step().unwrap();
+    cpu.step().unwrap();
+    // an 8-bit load leaves B alone
+    assert_eq!(cpu.registers.a, 0x1256);
+    assert_eq!(cpu.registers.pc, 0x0207);
+
+    cpu.step().unwrap();
+    assert_eq!(cpu.registers.x, 0x789A);
+    assert_eq!(cpu.registers.pc, 0x020A);
+
+    // 8-bit index registers lose their high byte
+    cpu.step().unwrap();
+    assert_eq!(cpu.registers.x, 0x009A);
+    cpu.step().unwrap();
+    assert_eq!(cpu.registers.x, 0x00BC);
+    assert_eq!(cpu.registers.pc, 0x020E);
+}
+
+/// Runs `opcode #operand` on a 16-bit accumulator in decimal mode and
+/// returns the accumulator and the N and C flags.
+fn decimal(opcode: u8, a: u16, operand: u16, carry: bool) -> (u16, u8) {
+    let mut cpu = setup_native(&[opcode, operand as u8, (operand >> 8) as u8]);
+    cpu.registers.a = a;
+    cpu.registers.p |= DECIMAL;
+    cpu.registers.set_flag(StatusFlag::Carry, carry);
+
+    cpu.step().unwrap();
+    assert_eq!(cpu.registers.pc, 0x0203);
+    (cpu.registers.a, cpu.registers.p & (NEGATIVE | CARRY))
+}
+
+#[test]
+fn sixteen_bit_decimal_arithmetic() {
+    // ADC
+    assert_eq!(decimal(0x69, 0x1234, 0x4321, false), (0x5555, 0));
+    assert_eq!(decimal(0x69, 0x8766, 0x1234, true), (0x0001, CARRY));
+    assert_eq!(decimal(0x69, 0x0999, 0x0001, false), (0x1000, 0));
+    assert_eq!(decimal(0x69, 0x4999, 0x3001, false), (0x8000, NEGATIVE));
+
+    // SBC
+    assert_eq!(decimal(0xE9, 0x1000, 0x0001, true), (0x0999, CARRY));
+    assert_eq!(decimal(0xE9, 0x5555, 0x1234, false), (0x4320, CARRY));
+    assert_eq!(decimal(0xE9, 0x0000, 0x0001, true), (0x9999, NEGATIVE));
+}
+
+#[test]
+fn mvn_copies_upwards() {
+    // MVN $02,$01 copies three bytes from $01:1000 to $02:2000
+    let mut cpu = setup_native(&[0x54, 0x02, 0x01]);
+    for (offset, byte) in [0x11, 0x22, 0x33].iter().enumerate() {
+        cpu.memory.write_byte(0x01_1000 + offset as u32, *byte);
+    }
+    cpu.registers.x = 0x1000;
+    cpu.registers.y = 0x2000;
+    cpu.registers.a = 2;
+
+    // one byte per step, the instruction repeats until C runs out
+    for _ in 0..3 {
+        assert_eq!(cpu.registers.pc, 0x0200);
+        assert_eq!(cpu.step().unwrap(), 7);
+    }
+    assert_eq!(cpu.registers.pc, 0x0203);
+
+    let copied: Vec<u8> = (0..3)
+        .map(|offset| cpu.memory.read_byte(0x02_2000 + offset))
+        .collect();
+    assert_eq!(copied, [0x11, 0x22, 0x33]);
+    assert_eq!(cpu.registers.a, 0xFFFF);
+    assert_eq!((cpu.registers.x, cpu.registers.y), (0x1003, 0x2003));
+    assert_eq!(cpu.registers.dbr, 0x02);
+}
+
+#[test]
+fn mvp_copies_downwards() {
+    // MVP $02,$01 with X and Y at the last bytes
+    let mut cpu = setup_native(&[0x44, 0x02, 0x01]);
+    for (offset, byte) in [0x11, 0x22, 0x33].iter().enumerate() {
+        cpu.memory.write_byte(0x01_1000 + offset as u32, *byte);
+    }
+    cpu.registers.x = 0x1002;
+    cpu.registers.y = 0x2002;
+    cpu.registers.a = 2;
+
+    while cpu.registers.pc == 0x0200 {
+        cpu.step().unwrap();
+    }
+
+    let copied: Vec<u8> = (0..3)
+        .map(|offset| cpu.memory.read_byte(0x02_2000 + offset))
+        .collect();
+    assert_eq!(copied, [0x11, 0x22, 0x33]);
+    assert_eq!((cpu.registers.x, cpu.registers.y), (0x0FFF, 0x1FFF));
+}
+
+#[test]
+fn jsl_and_rtl_cross_banks() {
+    // JSL $03:4000, with an RTL there
+    let mut cpu = setup(&[0x22, 0x00, 0x40, 0x03]);
+    cpu.memory.write_byte(0x03_4000, 0x6B);
+
+    assert_eq!(cpu.step().unwrap(), 8);
+    assert_eq!((cpu.registers.pbr, cpu.registers.pc), (0x03, 0x4000));
+    // the program bank, then the address of the last byte of the JSL
+    assert_eq!(stack(&cpu), [0x03, 0x02, 0x00]);
+
+    assert_eq!(cpu.step().unwrap(), 6);
+    assert_eq!((cpu.registers.pbr, cpu.registers.pc), (0x00, 0x0204));
+    assert_eq!(cpu.registers.sp, 0x01FF);
+}
+
+#[test]
+fn pea_pei_and_per_push_words() {
+    // PEA $1234
+    let mut cpu = setup_native(&[0xF4, 0x34, 0x12]);
+    assert_eq!(cpu.step().unwrap(), 5);
+    assert_eq!(stack(&cpu), [0x34, 0x12]);
+
+    // PEI ($10), which pushes the word at the direct page address
+    let mut cpu = setup_native(&[0xD4, 0x10]);
+    cpu.memory.write_word(0x0010, 0xBEEF);
+    assert_eq!(cpu.step().unwrap(), 6);
+    assert_eq!(stack(&cpu), [0xEF, 0xBE]);
+
+    // the same with a direct page off a page boundary costs a cycle more
+    let mut cpu = setup_native(&[0xD4, 0x10]);
+    cpu.registers.d = 0x0001;
+    cpu.memory.write_word(0x0011, 0xBEEF);
+    assert_eq!(cpu.step().unwrap(), 7);
+    assert_eq!(stack(&cpu), [0xEF, 0xBE]);
+
+    // PER $0100, relative to the next instruction at $0203
+    let mut cpu = setup_native(&[0x62, 0x00, 0x01]);
+    assert_eq!(cpu.step().unwrap(), 6);
+    assert_eq!(stack(&cpu), [0x03, 0x03]);
+    assert_eq!(cpu.registers.pc, 0x0203);
+}
+
+/// Points every vector at a different address, so that the one taken shows
+/// in pc afterwards.
+fn set_vectors(cpu: &mut Cpu) {
+    for vector in [
+        BRK_NATIVE, COP, COP_NATIVE, IRQ, IRQ_NATIVE, NMI, NMI_NATIVE,
+    ] {
+        cpu.memory.write_word(vector as u32, vector);
+    }
+}
+
+#[test]
+fn brk_and_cop_in_emulation_mode() {
+    // BRK shares the IRQ vector and sets B in the pushed copy of P
+    let mut cpu = setup(&[0x00, 0xEA]);
+    set_vectors(&mut cpu);
+    assert_eq!(cpu.step().unwrap(), 7);
+    assert_eq!(cpu.registers.pc, IRQ);
+    let pushed = stack(&cpu);
+    assert_eq!(pushed[1..], [0x02, 0x02]);
+    assert_ne!(pushed[0] & INDEX, 0);
+
+    // COP has a vector of its own and pushes B clear
+    let mut cpu = setup(&[0x02, 0xEA]);
+    set_vectors(&mut cpu);
+    assert_eq!(cpu.step().unwrap(), 7);
+    assert_eq!(cpu.registers.pc, COP);
+    let pushed = stack(&cpu);
+    assert_eq!(pushed[1..], [0x02, 0x02]);
+    assert_eq!(pushed[0] & INDEX, 0);
+}
+
+#[test]
+fn brk_and_cop_in_native_mode() {
+    for (opcode, vector) in [(0x00, BRK_NATIVE), (0x02, COP_NATIVE)] {
+        let mut cpu = setup_native(&[opcode, 0xEA]);
+        set_vectors(&mut cpu);
+        cpu.registers.pbr = 0x05;
+        cpu.memory.write_byte(0x05_0200, opcode);
+        cpu.registers.set_flag(StatusFlag::Decimal, true);
+
+        // the program bank is pushed as well, and cleared
+        assert_eq!(cpu.step().unwrap(), 8, "{:02X}", opcode);
+        assert_eq!((cpu.registers.pbr, cpu.registers.pc), (0x00, vector));
+        let pushed = stack(&cpu);
+        assert_eq!(pushed[1..], [0x02, 0x02, 0x05], "{:02X}", opcode);
+        assert!(!cpu.registers.get_flag(StatusFlag::Decimal));
+
+        // RTI takes the bank back off the stack
+        cpu.memory.write_byte(vector as u32, 0x40);
+        assert_eq!(cpu.step().unwrap(), 7);
+        assert_eq!((cpu.registers.pbr, cpu.registers.pc), (0x05, 0x0202));
+        assert!(cpu.registers.get_flag(StatusFlag::Decimal));
+    }
+}
+
+#[test]
+fn interrupt_vectors_follow_the_mode() {
+    let cases = [(true, NMI, IRQ, 7), (false, NMI_NATIVE, IRQ_NATIVE, 8)];
+
+    for (emulation, nmi, irq, cycles) in cases {
+        let mut cpu = if emulation {
+            setup(&[0xEA])
+        } else {
+            setup_native(&[0xEA])
+        };
+        set_vectors(&mut cpu);
+        cpu.registers.set_flag(StatusFlag::NoInterrupts, false);
+        cpu.trigger_nmi();
+        assert_eq!(cpu.step().unwrap(), cycles);
+        assert_eq!(cpu.registers.pc, nmi);
+
+        cpu.registers.set_flag(StatusFlag::NoInterrupts, false);
+        cpu.assert_irq();
+        assert_eq!(cpu.step().unwrap(), cycles);
+        assert_eq!(cpu.registers.pc, irq);
+    }
+}
+
+#[test]
+fn bra_cycles() {
+    // a taken BRA costs its base count, a page crossing only adds to it in
+    // emulation mode
+    for (displacement, emulation, cycles) in [(0x10, true, 3), (0xF0, true, 4), (0xF0, false, 3)] {
+        let mut cpu = if emulation {
+            setup(&[0x80, displacement])
+        } else {
+            setup_native(&[0x80, displacement])
+        };
+        assert_eq!(cpu.step().unwrap(), cycles, "{:02X}", displacement);
+        let target = 0x0202u16.wrapping_add(displacement as i8 as u16);
+        assert_eq!(cpu.registers.pc, target);
+    }
+}
+
+#[test]
+fn stp_ends_the_run() {
+    // XCE to native mode, then STP
+    let mut cpu = setup(&[0x18, 0xFB, 0xDB]);
+    cpu.memory.write_word(RESET as u32, 0x0200);
+
+    assert!(matches!(cpu.run(), Err(VolveError::Halted)));
+    assert_eq!(cpu.state(), State::Stopped);
+    assert!(!cpu.registers.emulation);
+}